@@ -14,8 +14,8 @@
 use stable_mir::ty::{
     Abi, AdtDef, Binder, BoundRegionKind, BoundTyKind, BoundVariableKind, ClosureKind, DynKind,
     ExistentialPredicate, ExistentialProjection, ExistentialTraitRef, FloatTy, FnSig,
-    GenericArgKind, GenericArgs, IndexedVal, IntTy, MirConst, Movability, Pattern, Region, RigidTy,
-    Span, TermKind, TraitRef, Ty, TyConst, UintTy, VariantDef, VariantIdx,
+    GenericArgKind, GenericArgs, IndexedVal, IntTy, MirConst, Movability, Pattern, Region, Reveal,
+    RigidTy, Span, TermKind, TraitRef, Ty, TyConst, UintTy, VariantDef, VariantIdx,
 };
 use stable_mir::{CrateItem, CrateNum, DefId};
 
@@ -215,6 +215,20 @@ fn internal<'tcx>(&self, _tables: &mut Tables<'_>, _tcx: TyCtxt<'tcx>) -> Self::
     }
 }
 
+impl RustcInternal for Reveal {
+    type T<'tcx> = rustc_ty::TypingEnv<'tcx>;
+
+    fn internal<'tcx>(&self, _tables: &mut Tables<'_>, _tcx: TyCtxt<'tcx>) -> Self::T<'tcx> {
+        match self {
+            Reveal::UserFacing => rustc_ty::TypingEnv {
+                typing_mode: rustc_ty::TypingMode::non_body_analysis(),
+                param_env: rustc_ty::ParamEnv::empty(),
+            },
+            Reveal::All => rustc_ty::TypingEnv::fully_monomorphized(),
+        }
+    }
+}
+
 impl RustcInternal for Movability {
     type T<'tcx> = rustc_ty::Movability;
 
@@ -532,7 +546,7 @@ fn internal<'tcx>(&self, tables: &mut Tables<'_>, tcx: TyCtxt<'tcx>) -> Self::T<
         match self {
             ProjectionElem::Deref => rustc_middle::mir::PlaceElem::Deref,
             ProjectionElem::Field(idx, ty) => {
-                rustc_middle::mir::PlaceElem::Field((*idx).into(), ty.internal(tables, tcx))
+                rustc_middle::mir::PlaceElem::Field(idx.to_index().into(), ty.internal(tables, tcx))
             }
             ProjectionElem::Index(idx) => rustc_middle::mir::PlaceElem::Index((*idx).into()),
             ProjectionElem::ConstantIndex { offset, min_length, from_end } => {
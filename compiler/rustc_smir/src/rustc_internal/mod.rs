@@ -419,6 +419,11 @@ pub fn create_or_fetch(&mut self, key: K) -> V {
         let v = self.index_map.entry(key).or_insert(V::to_val(len));
         *v
     }
+
+    /// Check whether a value with the given index has been created by this map.
+    pub fn contains_index(&self, index: usize) -> bool {
+        index < self.index_map.len()
+    }
 }
 
 impl<K: PartialEq + Hash + Eq, V: Copy + Debug + PartialEq + IndexedVal> Index<V>
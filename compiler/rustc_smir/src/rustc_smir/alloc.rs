@@ -1,9 +1,9 @@
-use rustc_abi::{Align, Size};
+use rustc_abi::Size;
 use rustc_middle::mir::ConstValue;
 use rustc_middle::mir::interpret::{AllocRange, Pointer, alloc_range};
 use stable_mir::Error;
 use stable_mir::mir::Mutability;
-use stable_mir::ty::{Allocation, ProvenanceMap};
+use stable_mir::ty::{Align, Allocation, ProvenanceMap};
 
 use crate::rustc_smir::{Stable, Tables};
 
@@ -12,7 +12,7 @@ fn new_empty_allocation(align: Align) -> Allocation {
     Allocation {
         bytes: Vec::new(),
         provenance: ProvenanceMap { ptrs: Vec::new() },
-        align: align.bytes(),
+        align,
         mutability: Mutability::Not,
     }
 }
@@ -56,7 +56,7 @@ pub(crate) fn try_new_allocation<'tcx>(
                 .layout_of(rustc_middle::ty::TypingEnv::fully_monomorphized().as_query_input(ty))
                 .map_err(|e| e.stable(tables))?
                 .align;
-            new_empty_allocation(align.abi)
+            new_empty_allocation(align.abi.stable(tables))
         }
         ConstValue::Slice { data, meta } => {
             let alloc_id = tables.tcx.reserve_and_set_memory_alloc(data);
@@ -132,7 +132,7 @@ pub(super) fn allocation_filter<'tcx>(
     Allocation {
         bytes,
         provenance: ProvenanceMap { ptrs },
-        align: alloc.align.bytes(),
+        align: alloc.align.stable(tables),
         mutability: alloc.mutability.stable(tables),
     }
 }
@@ -18,23 +18,32 @@
     GenericPredicates, Instance, List, ScalarInt, TyCtxt, TypeVisitableExt, ValTree,
 };
 use rustc_middle::{mir, ty};
+use rustc_session::config::EntryFnType;
+use rustc_span::DUMMY_SP;
 use rustc_span::def_id::LOCAL_CRATE;
+use rustc_span::hygiene::ExpnKind;
 use stable_mir::abi::{FnAbi, Layout, LayoutShape};
 use stable_mir::compiler_interface::Context;
 use stable_mir::mir::alloc::GlobalAlloc;
-use stable_mir::mir::mono::{InstanceDef, StaticDef};
+use stable_mir::mir::mono::{CodegenUnit, InstanceDef, StaticDef};
 use stable_mir::mir::{BinOp, Body, Place, UnOp};
 use stable_mir::target::{MachineInfo, MachineSize};
 use stable_mir::ty::{
-    AdtDef, AdtKind, Allocation, ClosureDef, ClosureKind, FieldDef, FnDef, ForeignDef,
-    ForeignItemKind, GenericArgs, IntrinsicDef, LineInfo, MirConst, PolyFnSig, RigidTy, Span, Ty,
-    TyConst, TyKind, UintTy, VariantDef,
+    AdtDef, AdtKind, Allocation, ClosureDef, ClosureKind, CoroutineWitnessDef, ExpnInfo, FieldDef,
+    FnDef, ForeignDef, ForeignItemKind, GenericArgs, IntrinsicDef, LineInfo, MirConst, PolyFnSig,
+    Reveal, RigidTy, Span, Ty, TyConst, TyKind, UintTy, UnevaluatedConst, VariantDef, VariantIdx,
+};
+use stable_mir::{
+    BodyAvailability, CodegenAttrs, Crate, CrateDef, CrateItem, CrateNum, DefId, DiagLevel, Error,
+    Filename, InlineAttr, ItemKind, Symbol, Visibility,
 };
-use stable_mir::{Crate, CrateDef, CrateItem, CrateNum, DefId, Error, Filename, ItemKind, Symbol};
 
 use crate::rustc_internal::RustcInternal;
 use crate::rustc_smir::builder::BodyBuilder;
-use crate::rustc_smir::{Stable, Tables, alloc, filter_def_ids, new_item_kind, smir_crate};
+use crate::rustc_smir::{
+    Stable, Tables, alloc, filter_def_ids, is_upstream_item_exported, new_item_kind,
+    replace_self_ty, smir_crate, visibility_owner,
+};
 
 impl<'tcx> Context for TablesWrapper<'tcx> {
     fn target_info(&self) -> MachineInfo {
@@ -53,6 +62,34 @@ fn entry_fn(&self) -> Option<stable_mir::CrateItem> {
         Some(tables.crate_item(tcx.entry_fn(())?.0))
     }
 
+    fn entry_instance(&self) -> Option<stable_mir::mir::mono::Instance> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let (entry_def_id, entry_type) = tcx.entry_fn(())?;
+        let instance = match entry_type {
+            EntryFnType::Main { .. } => {
+                // Execution starts at the `start` lang item, which is generic over the return type
+                // of `main`. Since `main` has no arguments, its return type cannot have late-bound
+                // regions.
+                let start_def_id = tcx.lang_items().start_fn()?;
+                let main_ret_ty = tcx.fn_sig(entry_def_id).no_bound_vars().unwrap().output();
+                let main_ret_ty = tcx.normalize_erasing_regions(
+                    ty::TypingEnv::fully_monomorphized(),
+                    main_ret_ty.no_bound_vars().unwrap(),
+                );
+                Instance::expect_resolve(
+                    tcx,
+                    ty::TypingEnv::fully_monomorphized(),
+                    start_def_id,
+                    tcx.mk_args(&[main_ret_ty.into()]),
+                    DUMMY_SP,
+                )
+            }
+            EntryFnType::Start => Instance::mono(tcx, entry_def_id),
+        };
+        Some(instance.stable(&mut *tables))
+    }
+
     fn all_local_items(&self) -> stable_mir::CrateItems {
         let mut tables = self.0.borrow_mut();
         tables.tcx.mir_keys(()).iter().map(|item| tables.crate_item(item.to_def_id())).collect()
@@ -71,6 +108,21 @@ fn has_body(&self, def: DefId) -> bool {
         tables.item_has_body(def_id)
     }
 
+    fn body_availability(&self, def: DefId) -> BodyAvailability {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let def_id = def.internal(&mut *tables, tcx);
+        if tcx.intrinsic(def_id).is_some_and(|intrinsic| intrinsic.must_be_overridden) {
+            BodyAvailability::Intrinsic
+        } else if tcx.is_mir_available(def_id) {
+            BodyAvailability::Available
+        } else if tcx.is_foreign_item(def_id) {
+            BodyAvailability::Foreign
+        } else {
+            BodyAvailability::NotEncoded
+        }
+    }
+
     fn foreign_modules(&self, crate_num: CrateNum) -> Vec<stable_mir::ty::ForeignModuleDef> {
         let mut tables = self.0.borrow_mut();
         let tcx = tables.tcx;
@@ -165,6 +217,49 @@ fn trait_impl(&self, impl_def: &stable_mir::ty::ImplDef) -> stable_mir::ty::Impl
         impl_trait.stable(&mut *tables)
     }
 
+    fn impl_associated_const(
+        &self,
+        impl_def: stable_mir::ty::ImplDef,
+        name: &str,
+    ) -> Result<MirConst, Error> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let impl_def_id = tables[impl_def.0];
+        if tcx.generics_of(impl_def_id).requires_monomorphization(tcx) {
+            return Err(Error::new(format!(
+                "Cannot evaluate constant `{name}` of generic impl `{}`",
+                tcx.def_path_str(impl_def_id)
+            )));
+        }
+        // For trait impls, look up the constant in the trait, so resolution picks either the
+        // value defined by the impl or the default value from the trait.
+        let (container, args) = match tcx.impl_trait_ref(impl_def_id) {
+            Some(trait_ref) => {
+                let trait_ref = trait_ref.instantiate_identity();
+                (trait_ref.def_id, trait_ref.args)
+            }
+            None => (impl_def_id, ty::GenericArgs::identity_for_item(tcx, impl_def_id)),
+        };
+        let Some(item) = tcx
+            .associated_items(container)
+            .filter_by_name_unhygienic(rustc_span::Symbol::intern(name))
+            .find(|item| item.kind == ty::AssocKind::Const)
+        else {
+            return Err(Error::new(format!(
+                "No associated constant `{name}` in `{}`",
+                tcx.def_path_str(impl_def_id)
+            )));
+        };
+        let typing_env = ty::TypingEnv::fully_monomorphized();
+        let const_ty = tcx.type_of(item.def_id).instantiate(tcx, args);
+        let const_ty = tcx.normalize_erasing_regions(typing_env, const_ty);
+        let uv = mir::UnevaluatedConst { def: item.def_id, args, promoted: None };
+        let value = tcx
+            .const_eval_resolve(typing_env, uv, tcx.def_span(item.def_id))
+            .map_err(|e| tables.const_eval_error(e, uv.def))?;
+        Ok(mir::Const::Val(value, const_ty).stable(&mut *tables))
+    }
+
     fn generics_of(&self, def_id: stable_mir::DefId) -> stable_mir::ty::Generics {
         let mut tables = self.0.borrow_mut();
         let def_id = tables[def_id];
@@ -303,6 +398,45 @@ fn get_lines(&self, span: &Span) -> LineInfo {
         LineInfo { start_line: lines.1, start_col: lines.2, end_line: lines.3, end_col: lines.4 }
     }
 
+    fn span_source_callsite(&self, span: Span) -> Span {
+        let mut tables = self.0.borrow_mut();
+        let callsite = tables[span].source_callsite();
+        callsite.stable(&mut *tables)
+    }
+
+    fn span_macro_backtrace(&self, span: Span) -> Vec<ExpnInfo> {
+        let mut tables = self.0.borrow_mut();
+        let backtrace: Vec<_> = tables[span].macro_backtrace().collect();
+        backtrace
+            .into_iter()
+            .filter_map(|expn_data| {
+                let ExpnKind::Macro(kind, name) = expn_data.kind else {
+                    return None;
+                };
+                Some(ExpnInfo {
+                    macro_name: name.stable(&mut *tables),
+                    kind: kind.stable(&mut *tables),
+                    call_site: expn_data.call_site.stable(&mut *tables),
+                    def_site: expn_data.def_site.stable(&mut *tables),
+                })
+            })
+            .collect()
+    }
+
+    fn emit_diagnostic(&self, span: Span, level: DiagLevel, message: &str) {
+        let tables = self.0.borrow();
+        let dcx = tables.tcx.dcx();
+        let span = tables[span];
+        let message = message.to_string();
+        match level {
+            DiagLevel::Error => {
+                dcx.span_err(span, message);
+            }
+            DiagLevel::Warning => dcx.span_warn(span, message),
+            DiagLevel::Note => dcx.span_note(span, message),
+        }
+    }
+
     fn item_kind(&self, item: CrateItem) -> ItemKind {
         let tables = self.0.borrow();
         new_item_kind(tables.tcx.def_kind(tables[item.0]))
@@ -313,6 +447,76 @@ fn is_foreign_item(&self, item: DefId) -> bool {
         tables.tcx.is_foreign_item(tables[item])
     }
 
+    fn foreign_link_name(&self, item: DefId) -> Option<Symbol> {
+        let tables = self.0.borrow();
+        let tcx = tables.tcx;
+        let def_id = tables[item];
+        if !tcx.is_foreign_item(def_id) {
+            return None;
+        }
+        let link_name = tcx.codegen_fn_attrs(def_id).link_name.unwrap_or(tcx.item_name(def_id));
+        Some(link_name.to_string())
+    }
+
+    fn codegen_attrs(&self, item: DefId) -> CodegenAttrs {
+        use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
+        let tables = self.0.borrow();
+        let tcx = tables.tcx;
+        let def_id = tables[item];
+        if !tcx.def_kind(def_id).has_codegen_attrs() {
+            return CodegenAttrs::default();
+        }
+        let attrs = tcx.codegen_fn_attrs(def_id);
+        CodegenAttrs {
+            naked: attrs.flags.contains(CodegenFnAttrFlags::NAKED),
+            no_mangle: attrs.flags.contains(CodegenFnAttrFlags::NO_MANGLE),
+            export_name: attrs.export_name.map(|name| name.to_string()),
+            inline: match attrs.inline {
+                rustc_attr_parsing::InlineAttr::None => InlineAttr::None,
+                rustc_attr_parsing::InlineAttr::Hint => InlineAttr::Hint,
+                rustc_attr_parsing::InlineAttr::Always => InlineAttr::Always,
+                rustc_attr_parsing::InlineAttr::Never => InlineAttr::Never,
+                rustc_attr_parsing::InlineAttr::Force { .. } => InlineAttr::Force,
+            },
+            target_features: attrs
+                .target_features
+                .iter()
+                .map(|feature| feature.name.to_string())
+                .collect(),
+        }
+    }
+
+    fn item_visibility(&self, item: DefId) -> Visibility {
+        use rustc_hir::def::DefKind;
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let def_id = visibility_owner(tcx, tables[item]);
+        match tcx.visibility(def_id) {
+            ty::Visibility::Public => Visibility::Public,
+            ty::Visibility::Restricted(module) => {
+                // Items are private if they are only visible in the module that contains them.
+                let parent_module =
+                    iter::successors(tcx.opt_parent(def_id), |id| tcx.opt_parent(*id))
+                        .find(|id| tcx.def_kind(*id) == DefKind::Mod);
+                if parent_module == Some(module) {
+                    Visibility::Private
+                } else {
+                    Visibility::Restricted(tables.create_def_id(module))
+                }
+            }
+        }
+    }
+
+    fn item_is_exported(&self, item: DefId) -> bool {
+        let tables = self.0.borrow();
+        let tcx = tables.tcx;
+        let def_id = tables[item];
+        match def_id.as_local() {
+            Some(local_def_id) => tcx.effective_visibilities(()).is_exported(local_def_id),
+            None => is_upstream_item_exported(tcx, def_id),
+        }
+    }
+
     fn foreign_item_kind(&self, def: ForeignDef) -> ForeignItemKind {
         let mut tables = self.0.borrow_mut();
         let def_id = tables[def.def_id()];
@@ -346,6 +550,31 @@ fn adt_is_simd(&self, def: AdtDef) -> bool {
         def.internal(&mut *tables, tcx).repr().simd()
     }
 
+    fn adt_has_drop_impl(&self, def: AdtDef) -> bool {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        def.internal(&mut *tables, tcx).has_dtor(tcx)
+    }
+
+    fn adt_transparent_field_ty(&self, def: AdtDef, args: &GenericArgs) -> Option<Ty> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let adt_def = def.internal(&mut *tables, tcx);
+        if !adt_def.repr().transparent() {
+            return None;
+        }
+        let args = args.internal(&mut *tables, tcx);
+        let typing_env = ty::TypingEnv::fully_monomorphized();
+        // Like the compiler, assume that fields whose layout cannot be computed are not 1-ZSTs.
+        let field_ty = adt_def
+            .all_fields()
+            .map(|field| tcx.normalize_erasing_regions(typing_env, field.ty(tcx, args)))
+            .find(|ty| {
+                !tcx.layout_of(typing_env.as_query_input(*ty)).is_ok_and(|layout| layout.is_1zst())
+            })?;
+        Some(field_ty.stable(&mut *tables))
+    }
+
     fn adt_is_cstr(&self, def: AdtDef) -> bool {
         let mut tables = self.0.borrow_mut();
         let tcx = tables.tcx;
@@ -385,6 +614,30 @@ fn closure_sig(&self, args: &GenericArgs) -> PolyFnSig {
         sig.stable(&mut *tables)
     }
 
+    fn closure_upvar_tys(&self, args: &GenericArgs) -> Vec<Ty> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let args_ref = args.internal(&mut *tables, tcx);
+        args_ref.as_closure().upvar_tys().iter().map(|ty| ty.stable(&mut *tables)).collect()
+    }
+
+    fn closure_parent(&self, def: ClosureDef) -> CrateItem {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let def_id = def.0.internal(&mut *tables, tcx);
+        tables.crate_item(tcx.parent(def_id))
+    }
+
+    fn coroutine_witness_tys(&self, def: CoroutineWitnessDef, args: &GenericArgs) -> Vec<Ty> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let def_id = def.0.internal(&mut *tables, tcx);
+        let args_ref = args.internal(&mut *tables, tcx);
+        let witness_tys: Vec<_> =
+            tcx.coroutine_hidden_types(def_id).map(|ty| ty.instantiate(tcx, args_ref)).collect();
+        witness_tys.iter().map(|ty| ty.stable(&mut *tables)).collect()
+    }
+
     fn adt_variants_len(&self, def: AdtDef) -> usize {
         let mut tables = self.0.borrow_mut();
         let tcx = tables.tcx;
@@ -558,6 +811,42 @@ fn ty_kind(&self, ty: stable_mir::ty::Ty) -> TyKind {
         tables.types[ty].kind().stable(&mut *tables)
     }
 
+    fn ty_replace_self(
+        &self,
+        ty: stable_mir::ty::Ty,
+        self_ty: stable_mir::ty::Ty,
+    ) -> stable_mir::ty::Ty {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let ty = ty.internal(&mut *tables, tcx);
+        let self_ty = self_ty.internal(&mut *tables, tcx);
+        replace_self_ty(tcx, ty, self_ty).stable(&mut *tables)
+    }
+
+    fn ty_const_replace_self(
+        &self,
+        ct: &stable_mir::ty::TyConst,
+        self_ty: stable_mir::ty::Ty,
+    ) -> stable_mir::ty::TyConst {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let ct = ct.internal(&mut *tables, tcx);
+        let self_ty = self_ty.internal(&mut *tables, tcx);
+        replace_self_ty(tcx, ct, self_ty).stable(&mut *tables)
+    }
+
+    fn mir_const_replace_self(
+        &self,
+        cnst: &stable_mir::ty::MirConst,
+        self_ty: stable_mir::ty::Ty,
+    ) -> stable_mir::ty::MirConst {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let cnst = cnst.internal(&mut *tables, tcx);
+        let self_ty = self_ty.internal(&mut *tables, tcx);
+        replace_self_ty(tcx, cnst, self_ty).stable(&mut *tables)
+    }
+
     fn ty_const_pretty(&self, ct: stable_mir::ty::TyConstId) -> String {
         let tables = self.0.borrow_mut();
         tables.ty_consts[ct].to_string()
@@ -592,6 +881,25 @@ fn instance_args(&self, def: InstanceDef) -> GenericArgs {
         instance.args.stable(&mut *tables)
     }
 
+    fn instance_instantiate_ty(
+        &self,
+        def: InstanceDef,
+        ty: stable_mir::ty::Ty,
+    ) -> Result<stable_mir::ty::Ty, Error> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let instance = tables.instances[def];
+        let ty = ty.internal(&mut *tables, tcx);
+        instance
+            .try_instantiate_mir_and_normalize_erasing_regions(
+                tcx,
+                ty::TypingEnv::fully_monomorphized(),
+                ty::EarlyBinder::bind(ty),
+            )
+            .map(|ty| ty.stable(&mut *tables))
+            .map_err(|err| Error::new(format!("Failed to instantiate `{ty}`: {err:?}")))
+    }
+
     fn instance_abi(&self, def: InstanceDef) -> Result<FnAbi, Error> {
         let mut tables = self.0.borrow_mut();
         let instance = tables.instances[def];
@@ -617,6 +925,41 @@ fn instance_mangled_name(&self, instance: InstanceDef) -> Symbol {
         tables.tcx.symbol_name(instance).name.to_string()
     }
 
+    fn codegen_units(&self) -> Vec<CodegenUnit> {
+        let mut tables = self.0.borrow_mut();
+        let (_, units) = tables.tcx.collect_and_partition_mono_items(());
+        units.iter().map(|unit| unit.stable(&mut *tables)).collect()
+    }
+
+    fn overflow_checks_enabled(&self) -> bool {
+        let tables = self.0.borrow();
+        tables.tcx.sess.overflow_checks()
+    }
+
+    fn debug_assertions_enabled(&self) -> bool {
+        let tables = self.0.borrow();
+        tables.tcx.sess.opts.debug_assertions
+    }
+
+    fn upstream_monomorphization(&self, def: InstanceDef) -> Option<Crate> {
+        let tables = self.0.borrow();
+        let tcx = tables.tcx;
+        let instance = tables.instances[def];
+        instance.upstream_monomorphization(tcx).map(|krate| smir_crate(tcx, krate))
+    }
+
+    fn is_drop_glue(&self, def: InstanceDef) -> bool {
+        let tables = self.0.borrow_mut();
+        let instance = tables.instances[def];
+        matches!(instance.def, ty::InstanceKind::DropGlue(..))
+    }
+
+    fn instance_requires_caller_location(&self, def: InstanceDef) -> bool {
+        let tables = self.0.borrow();
+        let instance = tables.instances[def];
+        instance.def.requires_caller_location(tables.tcx)
+    }
+
     fn is_empty_drop_shim(&self, def: InstanceDef) -> bool {
         let tables = self.0.borrow_mut();
         let instance = tables.instances[def];
@@ -723,7 +1066,53 @@ fn eval_instance(&self, def: InstanceDef, const_ty: Ty) -> Result<Allocation, Er
                     &mut *tables,
                 )
             })
-            .map_err(|e| e.stable(&mut *tables))?
+            .map_err(|e| tables.const_eval_error(e, instance.def_id()))?
+    }
+
+    fn eval_unevaluated_const(
+        &self,
+        cnst: &UnevaluatedConst,
+        args: &GenericArgs,
+        reveal: Reveal,
+    ) -> Result<MirConst, Error> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let def_id = tables[cnst.def.def_id()];
+        let args = args.internal(&mut *tables, tcx);
+        let const_args = ty::EarlyBinder::bind(cnst.args.internal(&mut *tables, tcx));
+        let const_args = const_args.instantiate(tcx, args);
+        let promoted = cnst.promoted.map(mir::Promoted::from_u32);
+        let const_ty = match promoted {
+            Some(promoted) => ty::EarlyBinder::bind(tcx.promoted_mir(def_id)[promoted].return_ty()),
+            None => tcx.type_of(def_id),
+        };
+        let typing_env = reveal.internal(&mut *tables, tcx);
+        let const_ty =
+            tcx.normalize_erasing_regions(typing_env, const_ty.instantiate(tcx, const_args));
+        let uv = mir::UnevaluatedConst { def: def_id, args: const_args, promoted };
+        let value = tcx
+            .const_eval_resolve(typing_env, uv, tcx.def_span(def_id))
+            .map_err(|e| tables.const_eval_error(e, uv.def))?;
+        Ok(mir::Const::Val(value, const_ty).stable(&mut *tables))
+    }
+
+    fn initializer_body(&self, def: DefId) -> Option<stable_mir::mir::Body> {
+        use rustc_hir::def::DefKind;
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let def_id = def.internal(&mut *tables, tcx);
+        let is_initializer = match tcx.def_kind(def_id) {
+            DefKind::Const | DefKind::AssocConst | DefKind::AnonConst | DefKind::InlineConst => {
+                true
+            }
+            DefKind::Static { .. } => !tcx.is_foreign_item(def_id),
+            _ => false,
+        };
+        if is_initializer && tcx.is_ctfe_mir_available(def_id) {
+            Some(tcx.mir_for_ctfe(def_id).stable(&mut *tables))
+        } else {
+            None
+        }
     }
 
     fn eval_static_initializer(&self, def: StaticDef) -> Result<Allocation, Error> {
@@ -756,6 +1145,56 @@ fn vtable_allocation(
         Some(alloc_id.stable(&mut *tables))
     }
 
+    fn supertrait_vtable_slot(&self, source: Ty, target: Ty) -> Result<Option<usize>, Error> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let source = tcx.erase_regions(source.internal(&mut *tables, tcx));
+        let target = tcx.erase_regions(target.internal(&mut *tables, tcx));
+        if source.has_non_region_param() || target.has_non_region_param() {
+            return Err(Error::new(format!(
+                "Expected monomorphic trait objects, but found `{source}` and `{target}`"
+            )));
+        }
+        let (ty::Dynamic(source_preds, ..), ty::Dynamic(target_preds, ..)) =
+            (source.kind(), target.kind())
+        else {
+            return Err(Error::new(format!(
+                "Expected trait objects, but found `{source}` and `{target}`"
+            )));
+        };
+        // The query expects the target principal, if any, to be a supertrait of the source one.
+        if let Some(target_principal) = target_preds.principal_def_id() {
+            let is_supertrait = source_preds.principal_def_id().is_some_and(|source_principal| {
+                tcx.supertrait_def_ids(source_principal).any(|def_id| def_id == target_principal)
+            });
+            if !is_supertrait {
+                return Err(Error::new(format!("`{source}` cannot be upcast to `{target}`")));
+            }
+        }
+        Ok(tcx.supertrait_vtable_slot((source, target)))
+    }
+
+    fn same_global_alloc(
+        &self,
+        lhs: stable_mir::mir::alloc::AllocId,
+        rhs: stable_mir::mir::alloc::AllocId,
+    ) -> bool {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let lhs = lhs.internal(&mut *tables, tcx);
+        let rhs = rhs.internal(&mut *tables, tcx);
+        // Memory allocations are interned, so this doesn't compare their contents.
+        lhs == rhs || tcx.global_alloc(lhs) == tcx.global_alloc(rhs)
+    }
+
+    fn alloc_id_exists(&self, index: usize) -> bool {
+        self.0.borrow().alloc_ids.contains_index(index)
+    }
+
+    fn def_id_exists(&self, index: usize) -> bool {
+        self.0.borrow().def_ids.contains_index(index)
+    }
+
     fn krate(&self, def_id: stable_mir::DefId) -> Crate {
         let tables = self.0.borrow();
         smir_crate(tables.tcx, tables[def_id].krate)
@@ -778,6 +1217,17 @@ fn instance_name(&self, def: InstanceDef, trimmed: bool) -> Symbol {
         }
     }
 
+    fn normalize_ty(&self, ty: Ty, reveal: Reveal) -> Result<Ty, Error> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let ty = ty.internal(&mut *tables, tcx);
+        let typing_env = reveal.internal(&mut *tables, tcx);
+        let normalized = tcx
+            .try_normalize_erasing_regions(typing_env, ty)
+            .map_err(|_| Error::new(format!("Failed to normalize `{ty}`")))?;
+        Ok(normalized.stable(&mut *tables))
+    }
+
     fn ty_layout(&self, ty: Ty) -> Result<Layout, Error> {
         let mut tables = self.0.borrow_mut();
         let tcx = tables.tcx;
@@ -786,6 +1236,28 @@ fn ty_layout(&self, ty: Ty) -> Result<Layout, Error> {
         Ok(layout.stable(&mut *tables))
     }
 
+    fn ty_is_uninhabited(&self, ty: Ty) -> bool {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let ty = ty.internal(&mut *tables, tcx);
+        ty.is_privately_uninhabited(tcx, ty::TypingEnv::fully_monomorphized())
+    }
+
+    fn ty_discriminants(&self, ty: Ty) -> Option<Vec<(VariantIdx, u128)>> {
+        let mut tables = self.0.borrow_mut();
+        let tcx = tables.tcx;
+        let ty = ty.internal(&mut *tables, tcx);
+        let ty::Adt(def, _) = ty.kind() else { return None };
+        if !def.is_enum() {
+            return None;
+        }
+        Some(
+            def.discriminants(tcx)
+                .map(|(idx, discr)| (idx.stable(&mut *tables), discr.val))
+                .collect(),
+        )
+    }
+
     fn layout_shape(&self, id: Layout) -> LayoutShape {
         let mut tables = self.0.borrow_mut();
         let tcx = tables.tcx;
@@ -5,9 +5,9 @@
 use rustc_middle::ty;
 use rustc_target::callconv::{self, Conv};
 use stable_mir::abi::{
-    AddressSpace, ArgAbi, CallConvention, FieldsShape, FloatLength, FnAbi, IntegerLength, Layout,
-    LayoutShape, PassMode, Primitive, Scalar, TagEncoding, TyAndLayout, ValueAbi, VariantsShape,
-    WrappingRange,
+    AddressSpace, ArgAbi, ArgAttributes, ArgExtension, CallConvention, FieldsShape, FloatLength,
+    FnAbi, IntegerLength, Layout, LayoutShape, PassMode, Primitive, Scalar, TagEncoding,
+    TyAndLayout, ValueAbi, VariantsShape, WrappingRange,
 };
 use stable_mir::opaque;
 use stable_mir::target::MachineSize as Size;
@@ -123,25 +123,56 @@ fn stable(&self, _tables: &mut Tables<'_>) -> Self::T {
 impl<'tcx> Stable<'tcx> for callconv::PassMode {
     type T = PassMode;
 
-    fn stable(&self, _tables: &mut Tables<'_>) -> Self::T {
+    fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
         match self {
             callconv::PassMode::Ignore => PassMode::Ignore,
-            callconv::PassMode::Direct(attr) => PassMode::Direct(opaque(attr)),
+            callconv::PassMode::Direct(attr) => PassMode::Direct(attr.stable(tables)),
             callconv::PassMode::Pair(first, second) => {
-                PassMode::Pair(opaque(first), opaque(second))
+                PassMode::Pair(first.stable(tables), second.stable(tables))
             }
             callconv::PassMode::Cast { pad_i32, cast } => {
                 PassMode::Cast { pad_i32: *pad_i32, cast: opaque(cast) }
             }
             callconv::PassMode::Indirect { attrs, meta_attrs, on_stack } => PassMode::Indirect {
-                attrs: opaque(attrs),
-                meta_attrs: opaque(meta_attrs),
+                attrs: attrs.stable(tables),
+                meta_attrs: meta_attrs.stable(tables),
                 on_stack: *on_stack,
             },
         }
     }
 }
 
+impl<'tcx> Stable<'tcx> for callconv::ArgAttributes {
+    type T = ArgAttributes;
+
+    fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
+        use callconv::ArgAttribute;
+        ArgAttributes {
+            no_alias: self.regular.contains(ArgAttribute::NoAlias),
+            no_capture: self.regular.contains(ArgAttribute::NoCapture),
+            non_null: self.regular.contains(ArgAttribute::NonNull),
+            read_only: self.regular.contains(ArgAttribute::ReadOnly),
+            in_reg: self.regular.contains(ArgAttribute::InReg),
+            no_undef: self.regular.contains(ArgAttribute::NoUndef),
+            arg_ext: self.arg_ext.stable(tables),
+            pointee_size: self.pointee_size.stable(tables),
+            pointee_align: self.pointee_align.stable(tables),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for callconv::ArgExtension {
+    type T = ArgExtension;
+
+    fn stable(&self, _tables: &mut Tables<'_>) -> Self::T {
+        match self {
+            callconv::ArgExtension::None => ArgExtension::None,
+            callconv::ArgExtension::Zext => ArgExtension::Zext,
+            callconv::ArgExtension::Sext => ArgExtension::Sext,
+        }
+    }
+}
+
 impl<'tcx> Stable<'tcx> for rustc_abi::FieldsShape<rustc_abi::FieldIdx> {
     type T = FieldsShape;
 
@@ -152,9 +183,10 @@ fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
             rustc_abi::FieldsShape::Array { stride, count } => {
                 FieldsShape::Array { stride: stride.stable(tables), count: *count }
             }
-            rustc_abi::FieldsShape::Arbitrary { offsets, .. } => {
-                FieldsShape::Arbitrary { offsets: offsets.iter().as_slice().stable(tables) }
-            }
+            rustc_abi::FieldsShape::Arbitrary { offsets, memory_index } => FieldsShape::Arbitrary {
+                offsets: offsets.iter().as_slice().stable(tables),
+                memory_index: memory_index.iter().map(|idx| *idx as usize).collect(),
+            },
         }
     }
 }
@@ -227,7 +259,7 @@ impl<'tcx> Stable<'tcx> for rustc_abi::Align {
     type T = Align;
 
     fn stable(&self, _tables: &mut Tables<'_>) -> Self::T {
-        self.bytes()
+        Align::from_bytes(self.bytes()).unwrap()
     }
 }
 
@@ -5,8 +5,8 @@
 use rustc_middle::{bug, mir};
 use stable_mir::mir::alloc::GlobalAlloc;
 use stable_mir::mir::{ConstOperand, Statement, UserTypeProjection, VarDebugInfoFragment};
+use stable_mir::opaque;
 use stable_mir::ty::{Allocation, ConstantKind, MirConst};
-use stable_mir::{Error, opaque};
 
 use crate::rustc_smir::{Stable, Tables, alloc};
 
@@ -14,7 +14,7 @@ impl<'tcx> Stable<'tcx> for mir::Body<'tcx> {
     type T = stable_mir::mir::Body;
 
     fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
-        stable_mir::mir::Body::new(
+        let body = stable_mir::mir::Body::new(
             self.basic_blocks
                 .iter()
                 .map(|block| stable_mir::mir::BasicBlock {
@@ -39,6 +39,47 @@ fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
             self.spread_arg.stable(tables),
             self.span.stable(tables),
         )
+        .with_source_scopes(self.source_scopes.iter().map(|scope| scope.stable(tables)).collect());
+        match self.coroutine_layout_raw() {
+            Some(layout) => {
+                let mut layout = layout.stable(tables);
+                // The state transform turns the resume argument of `gen` coroutines into a
+                // regular local, and removes it from the arguments.
+                if self.arg_count < COROUTINE_RESUME_LOCAL {
+                    layout.resume_local = None;
+                }
+                // The resume local is always one of the arguments at this point.
+                body.with_coroutine_layout(layout).unwrap_or_else(|err| bug!("{err}"))
+            }
+            None => body,
+        }
+    }
+}
+
+/// The local that holds the resume argument of a coroutine body that has a layout.
+///
+/// The coroutine state transform, in `rustc_mir_transform/src/coroutine.rs`, always keeps the
+/// resume argument in `_2`, right after the return place and the coroutine itself. For `gen`
+/// coroutines, `transform_gen_context` keeps this local but drops it from the arguments.
+const COROUTINE_RESUME_LOCAL: stable_mir::mir::Local = 2;
+
+impl<'tcx> Stable<'tcx> for mir::CoroutineLayout<'tcx> {
+    type T = stable_mir::mir::CoroutineLayout;
+    fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
+        stable_mir::mir::CoroutineLayout {
+            field_tys: self.field_tys.iter().map(|field| field.ty.stable(tables)).collect(),
+            variant_fields: self
+                .variant_fields
+                .iter()
+                .map(|fields| fields.iter().map(|local| local.as_usize()).collect())
+                .collect(),
+            variant_spans: self
+                .variant_source_info
+                .iter()
+                .map(|info| info.span.stable(tables))
+                .collect(),
+            resume_local: Some(COROUTINE_RESUME_LOCAL),
+        }
     }
 }
 
@@ -69,6 +110,19 @@ fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
     }
 }
 
+impl<'tcx> Stable<'tcx> for mir::SourceScopeData<'tcx> {
+    type T = stable_mir::mir::SourceScopeData;
+    fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
+        stable_mir::mir::SourceScopeData {
+            span: self.span.stable(tables),
+            parent_scope: self.parent_scope.map(|scope| scope.into()),
+            inlined: self
+                .inlined
+                .map(|(instance, call_span)| (instance.stable(tables), call_span.stable(tables))),
+        }
+    }
+}
+
 impl<'tcx> Stable<'tcx> for mir::VarDebugInfoFragment<'tcx> {
     type T = stable_mir::mir::VarDebugInfoFragment;
     fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
@@ -548,7 +602,7 @@ fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
                     var_idx.stable(tables),
                     generic_arg.stable(tables),
                     user_ty_index.map(|idx| idx.index()),
-                    field_idx.map(|idx| idx.index()),
+                    field_idx.map(|idx| idx.stable(tables)),
                 )
             }
             mir::AggregateKind::Closure(def_id, generic_arg) => {
@@ -581,20 +635,51 @@ impl<'tcx> Stable<'tcx> for mir::InlineAsmOperand<'tcx> {
     type T = stable_mir::mir::InlineAsmOperand;
     fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
         use rustc_middle::mir::InlineAsmOperand;
+        use stable_mir::mir::InlineAsmDirection;
 
-        let (in_value, out_place) = match self {
-            InlineAsmOperand::In { value, .. } => (Some(value.stable(tables)), None),
-            InlineAsmOperand::Out { place, .. } => (None, place.map(|place| place.stable(tables))),
-            InlineAsmOperand::InOut { in_value, out_place, .. } => {
-                (Some(in_value.stable(tables)), out_place.map(|place| place.stable(tables)))
+        let (in_value, out_place, direction, reg) = match self {
+            InlineAsmOperand::In { reg, value } => {
+                (Some(value.stable(tables)), None, Some(InlineAsmDirection::In), Some(reg))
             }
+            InlineAsmOperand::Out { reg, late, place } => (
+                None,
+                place.map(|place| place.stable(tables)),
+                Some(InlineAsmDirection::Out { late: *late }),
+                Some(reg),
+            ),
+            InlineAsmOperand::InOut { reg, late, in_value, out_place } => (
+                Some(in_value.stable(tables)),
+                out_place.map(|place| place.stable(tables)),
+                Some(InlineAsmDirection::InOut { late: *late }),
+                Some(reg),
+            ),
             InlineAsmOperand::Const { .. }
             | InlineAsmOperand::SymFn { .. }
             | InlineAsmOperand::SymStatic { .. }
-            | InlineAsmOperand::Label { .. } => (None, None),
+            | InlineAsmOperand::Label { .. } => (None, None, None, None),
         };
 
-        stable_mir::mir::InlineAsmOperand { in_value, out_place, raw_rpr: format!("{self:?}") }
+        stable_mir::mir::InlineAsmOperand {
+            in_value,
+            out_place,
+            direction,
+            reg: reg.map(|reg| reg.stable(tables)),
+            raw_rpr: format!("{self:?}"),
+        }
+    }
+}
+
+impl<'tcx> Stable<'tcx> for rustc_target::asm::InlineAsmRegOrRegClass {
+    type T = stable_mir::mir::InlineAsmReg;
+    fn stable(&self, _: &mut Tables<'_>) -> Self::T {
+        use rustc_target::asm::InlineAsmRegOrRegClass;
+        use stable_mir::mir::InlineAsmReg;
+        match self {
+            InlineAsmRegOrRegClass::Reg(reg) => InlineAsmReg::Reg(reg.name().to_string()),
+            InlineAsmRegOrRegClass::RegClass(class) => {
+                InlineAsmReg::RegClass(class.name().to_string())
+            }
+        }
     }
 }
 
@@ -650,7 +735,10 @@ fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
                 target: target.map(|t| t.as_usize()),
                 unwind: unwind.stable(tables),
             },
-            mir::TerminatorKind::TailCall { func: _, args: _, fn_span: _ } => todo!(),
+            mir::TerminatorKind::TailCall { func, args, fn_span: _ } => TerminatorKind::TailCall {
+                func: func.stable(tables),
+                args: args.iter().map(|arg| arg.node.stable(tables)).collect(),
+            },
             mir::TerminatorKind::Assert { cond, expected, msg, target, unwind } => {
                 TerminatorKind::Assert {
                     cond: cond.stable(tables),
@@ -734,11 +822,17 @@ impl<'tcx> Stable<'tcx> for rustc_middle::mir::Const<'tcx> {
     fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
         let id = tables.intern_mir_const(tables.tcx.lift(*self).unwrap());
         match *self {
-            mir::Const::Ty(ty, c) => MirConst::new(
-                stable_mir::ty::ConstantKind::Ty(c.stable(tables)),
-                ty.stable(tables),
-                id,
-            ),
+            mir::Const::Ty(ty, c) => {
+                // Uses of const generic parameters are reported as such, so users don't need to
+                // inspect the type system constant.
+                let kind = match c.kind() {
+                    rustc_middle::ty::ConstKind::Param(param) => {
+                        ConstantKind::Param(param.stable(tables))
+                    }
+                    _ => ConstantKind::Ty(c.stable(tables)),
+                };
+                MirConst::new(kind, ty.stable(tables), id)
+            }
             mir::Const::Unevaluated(unev_const, ty) => {
                 let kind =
                     stable_mir::ty::ConstantKind::Unevaluated(stable_mir::ty::UnevaluatedConst {
@@ -764,14 +858,6 @@ fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
     }
 }
 
-impl<'tcx> Stable<'tcx> for mir::interpret::ErrorHandled {
-    type T = Error;
-
-    fn stable(&self, _tables: &mut Tables<'_>) -> Self::T {
-        Error::new(format!("{self:?}"))
-    }
-}
-
 impl<'tcx> Stable<'tcx> for MonoItem<'tcx> {
     type T = stable_mir::mir::mono::MonoItem;
 
@@ -784,3 +870,14 @@ fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
         }
     }
 }
+
+impl<'tcx> Stable<'tcx> for rustc_middle::mir::mono::CodegenUnit<'tcx> {
+    type T = stable_mir::mir::mono::CodegenUnit;
+
+    fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
+        stable_mir::mir::mono::CodegenUnit {
+            name: self.name().to_string(),
+            items: self.items().keys().map(|item| item.stable(tables)).collect(),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Conversion of internal Rust compiler items to stable ones.
 
 use rustc_abi::FieldIdx;
+use stable_mir::ty::IndexedVal;
 
 use crate::rustc_smir::{Stable, Tables};
 
@@ -22,9 +23,9 @@ fn stable(&self, _: &mut Tables<'_>) -> Self::T {
 }
 
 impl<'tcx> Stable<'tcx> for FieldIdx {
-    type T = usize;
+    type T = stable_mir::mir::FieldIdx;
     fn stable(&self, _: &mut Tables<'_>) -> Self::T {
-        self.as_usize()
+        stable_mir::mir::FieldIdx::to_val(self.as_usize())
     }
 }
 
@@ -85,3 +86,16 @@ fn stable(&self, tables: &mut Tables<'_>) -> Self::T {
         tables.create_span(*self)
     }
 }
+
+impl<'tcx> Stable<'tcx> for rustc_span::hygiene::MacroKind {
+    type T = stable_mir::ty::MacroKind;
+
+    fn stable(&self, _tables: &mut Tables<'_>) -> Self::T {
+        use rustc_span::hygiene::MacroKind;
+        match self {
+            MacroKind::Bang => stable_mir::ty::MacroKind::Bang,
+            MacroKind::Attr => stable_mir::ty::MacroKind::Attr,
+            MacroKind::Derive => stable_mir::ty::MacroKind::Derive,
+        }
+    }
+}
@@ -11,9 +11,11 @@
 
 use rustc_hir::def::DefKind;
 use rustc_middle::mir;
-use rustc_middle::mir::interpret::AllocId;
-use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
+use rustc_middle::mir::interpret::{AllocId, ErrorHandled};
+use rustc_middle::ty::fold::BottomUpFolder;
+use rustc_middle::ty::{self, Instance, Ty, TyCtxt, TypeFoldable};
 use rustc_span::def_id::{CrateNum, DefId, LOCAL_CRATE};
+use rustc_span::symbol::kw;
 use stable_mir::abi::Layout;
 use stable_mir::mir::mono::{InstanceDef, StaticDef};
 use stable_mir::ty::{FnDef, MirConstId, Span, TyConstId};
@@ -91,6 +93,30 @@ fn to_fn_def(&mut self, def_id: DefId) -> Option<FnDef> {
     fn to_static(&mut self, def_id: DefId) -> Option<StaticDef> {
         matches!(self.tcx.def_kind(def_id), DefKind::Static { .. }).then(|| self.static_def(def_id))
     }
+
+    /// Build the error for a constant that failed to evaluate.
+    ///
+    /// The compiler has already reported the details of the failure to the user, so the error
+    /// points to the location of the failure, or to the constant `def_id` if the compiler didn't
+    /// record one.
+    pub(crate) fn const_eval_error(
+        &mut self,
+        error: ErrorHandled,
+        def_id: DefId,
+    ) -> stable_mir::Error {
+        let name = self.tcx.def_path_str(def_id);
+        let (msg, span) = match error {
+            ErrorHandled::Reported(_, span) => (
+                format!("Failed to evaluate constant `{name}`. The compiler reported an error"),
+                span,
+            ),
+            ErrorHandled::TooGeneric(span) => {
+                (format!("Failed to evaluate constant `{name}`. The constant is too generic"), span)
+            }
+        };
+        let span = if span.is_dummy() { self.tcx.def_span(def_id) } else { span };
+        stable_mir::Error::with_span(msg, span.stable(self))
+    }
 }
 
 /// Iterate over the definitions of the given crate.
@@ -111,6 +137,73 @@ pub(crate) fn filter_def_ids<F, T>(tcx: TyCtxt<'_>, krate: CrateNum, mut func: F
     }
 }
 
+/// Find the item that determines the visibility of the given definition.
+///
+/// Definitions such as closures and anonymous constants don't have a visibility of their own.
+/// They are only reachable through the item that contains them, so we use that item instead.
+pub(crate) fn visibility_owner(tcx: TyCtxt<'_>, def_id: DefId) -> DefId {
+    std::iter::successors(Some(def_id), |id| tcx.opt_parent(*id))
+        .find(|id| {
+            !matches!(
+                tcx.def_kind(*id),
+                DefKind::Closure
+                    | DefKind::AnonConst
+                    | DefKind::InlineConst
+                    | DefKind::SyntheticCoroutineBody
+                    | DefKind::OpaqueTy
+                    | DefKind::Static { nested: true, .. }
+            )
+        })
+        .unwrap_or(def_id)
+}
+
+/// Whether an item from an upstream crate can be reached from outside that crate.
+///
+/// Effective visibilities are only computed for the local crate. For upstream crates, we use the
+/// visible parent map, which follows the public children of each module and thus accounts for
+/// re-exports. Items that are not in that map are reachable if their parents are.
+pub(crate) fn is_upstream_item_exported(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    // Definitions without a visibility of their own, such as closures, are not exported.
+    if visibility_owner(tcx, def_id) != def_id || !tcx.visibility(def_id).is_public() {
+        return false;
+    }
+    match tcx.opt_parent(def_id) {
+        // The crate root.
+        None => true,
+        // Associated items are reachable through the trait and the type of their impl.
+        Some(parent) if matches!(tcx.def_kind(parent), DefKind::Impl { .. }) => {
+            let trait_exported = tcx.impl_trait_ref(parent).is_none_or(|trait_ref| {
+                is_upstream_item_exported(tcx, trait_ref.skip_binder().def_id)
+            });
+            let self_ty = tcx.type_of(parent).instantiate_identity();
+            let ty_exported =
+                self_ty.ty_adt_def().is_none_or(|adt| is_upstream_item_exported(tcx, adt.did()));
+            trait_exported && ty_exported
+        }
+        Some(parent) => {
+            tcx.visible_parent_map(()).contains_key(&def_id)
+                || is_upstream_item_exported(tcx, parent)
+        }
+    }
+}
+
+/// Replace the `Self` type parameter of a trait by the given type.
+pub(crate) fn replace_self_ty<'tcx, T: TypeFoldable<TyCtxt<'tcx>>>(
+    tcx: TyCtxt<'tcx>,
+    value: T,
+    self_ty: Ty<'tcx>,
+) -> T {
+    value.fold_with(&mut BottomUpFolder {
+        tcx,
+        ty_op: |ty| match ty.kind() {
+            ty::Param(param) if param.index == 0 && param.name == kw::SelfUpper => self_ty,
+            _ => ty,
+        },
+        lt_op: |lt| lt,
+        ct_op: |ct| ct,
+    })
+}
+
 /// Build a stable mir crate from a given crate number.
 pub(crate) fn smir_crate(tcx: TyCtxt<'_>, crate_num: CrateNum) -> stable_mir::Crate {
     let crate_name = tcx.crate_name(crate_num).to_string();
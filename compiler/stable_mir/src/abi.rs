@@ -49,15 +49,61 @@ pub enum PassMode {
     /// Pass the argument directly.
     ///
     /// The argument has a layout abi of `Scalar` or `Vector`.
-    Direct(Opaque),
+    Direct(ArgAttributes),
     /// Pass a pair's elements directly in two arguments.
     ///
     /// The argument has a layout abi of `ScalarPair`.
-    Pair(Opaque, Opaque),
+    Pair(ArgAttributes, ArgAttributes),
     /// Pass the argument after casting it.
     Cast { pad_i32: bool, cast: Opaque },
     /// Pass the argument indirectly via a hidden pointer.
-    Indirect { attrs: Opaque, meta_attrs: Opaque, on_stack: bool },
+    ///
+    /// The `meta_attrs` are the attributes of the metadata of unsized arguments.
+    Indirect { attrs: ArgAttributes, meta_attrs: Option<ArgAttributes>, on_stack: bool },
+}
+
+/// The attributes the compiler derives for an argument that is passed directly or by pointer.
+///
+/// These correspond to the attributes used by code generation, e.g., a `&u8` argument is
+/// `noalias`, `readonly`, and it is dereferenceable for 1 byte.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct ArgAttributes {
+    /// The pointer argument does not alias any other pointer accessed by the function.
+    pub no_alias: bool,
+    /// The function does not keep a copy of the pointer argument after it returns.
+    pub no_capture: bool,
+    /// The pointer argument is never null.
+    pub non_null: bool,
+    /// The function does not write through the pointer argument.
+    pub read_only: bool,
+    /// The argument is passed in a register.
+    pub in_reg: bool,
+    /// The argument does not contain uninitialized bytes.
+    pub no_undef: bool,
+    /// How small integer arguments are extended to the size of a register.
+    pub arg_ext: ArgExtension,
+    /// The number of bytes that can be accessed through the pointer argument, if it is not null.
+    pub pointee_size: Size,
+    /// The minimum alignment of the pointee, if known.
+    pub pointee_align: Option<Align>,
+}
+
+impl ArgAttributes {
+    /// Whether the pointer argument is known to point to at least one valid byte if it is not
+    /// null.
+    pub fn is_dereferenceable(&self) -> bool {
+        self.pointee_size.bytes() > 0
+    }
+}
+
+/// How an integer argument is extended when the ABI requires it to be passed in a larger register.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum ArgExtension {
+    None,
+    /// Zero extension.
+    Zext,
+    /// Sign extension.
+    Sext,
 }
 
 /// The layout of a type, alongside the type itself.
@@ -84,7 +130,7 @@ pub struct LayoutShape {
     /// The `abi` defines how this data is passed between functions.
     pub abi: ValueAbi,
 
-    /// The ABI mandated alignment in bytes.
+    /// The ABI mandated alignment.
     pub abi_align: Align,
 
     /// The size of this layout in bytes.
@@ -105,7 +151,7 @@ pub fn is_sized(&self) -> bool {
 
     /// Returns `true` if the type is sized and a 1-ZST (meaning it has size 0 and alignment 1).
     pub fn is_1zst(&self) -> bool {
-        self.is_sized() && self.size.bits() == 0 && self.abi_align == 1
+        self.is_sized() && self.size.bits() == 0 && self.abi_align.bytes() == 1
     }
 }
 
@@ -152,6 +198,12 @@ pub enum FieldsShape {
         /// I.e.: It follows the same order as [crate::ty::VariantDef::fields()].
         /// This vector does not go in increasing order.
         offsets: Vec<Size>,
+
+        /// Maps each field, in source definition order, to its position in memory order.
+        ///
+        /// E.g.: the compiler may place the fields of `struct S { a: u8, b: u64, c: u8 }` as
+        /// `b, a, c` to reduce padding, in which case this is `[1, 0, 2]`.
+        memory_index: Vec<usize>,
     },
 }
 
@@ -159,10 +211,12 @@ impl FieldsShape {
     pub fn fields_by_offset_order(&self) -> Vec<FieldIdx> {
         match self {
             FieldsShape::Primitive => vec![],
-            FieldsShape::Union(_) | FieldsShape::Array { .. } => (0..self.count()).collect(),
+            FieldsShape::Union(_) | FieldsShape::Array { .. } => {
+                (0..self.count()).map(FieldIdx::to_val).collect()
+            }
             FieldsShape::Arbitrary { offsets, .. } => {
-                let mut indices = (0..offsets.len()).collect::<Vec<_>>();
-                indices.sort_by_key(|idx| offsets[*idx]);
+                let mut indices = (0..offsets.len()).map(FieldIdx::to_val).collect::<Vec<_>>();
+                indices.sort_by_key(|idx| offsets[idx.to_index()]);
                 indices
             }
         }
@@ -8,24 +8,27 @@
 use crate::abi::{FnAbi, Layout, LayoutShape};
 use crate::crate_def::Attribute;
 use crate::mir::alloc::{AllocId, GlobalAlloc};
-use crate::mir::mono::{Instance, InstanceDef, StaticDef};
+use crate::mir::mono::{CodegenUnit, Instance, InstanceDef, StaticDef};
 use crate::mir::{BinOp, Body, Place, UnOp};
 use crate::target::MachineInfo;
 use crate::ty::{
-    AdtDef, AdtKind, Allocation, ClosureDef, ClosureKind, FieldDef, FnDef, ForeignDef,
-    ForeignItemKind, ForeignModule, ForeignModuleDef, GenericArgs, GenericPredicates, Generics,
-    ImplDef, ImplTrait, IntrinsicDef, LineInfo, MirConst, PolyFnSig, RigidTy, Span, TraitDecl,
-    TraitDef, Ty, TyConst, TyConstId, TyKind, UintTy, VariantDef,
+    AdtDef, AdtKind, Allocation, ClosureDef, ClosureKind, CoroutineWitnessDef, ExpnInfo, FieldDef,
+    FnDef, ForeignDef, ForeignItemKind, ForeignModule, ForeignModuleDef, GenericArgs,
+    GenericPredicates, Generics, ImplDef, ImplTrait, IntrinsicDef, LineInfo, MirConst, PolyFnSig,
+    Reveal, RigidTy, Span, TraitDecl, TraitDef, Ty, TyConst, TyConstId, TyKind, UintTy,
+    UnevaluatedConst, VariantDef, VariantIdx,
 };
 use crate::{
-    Crate, CrateItem, CrateItems, CrateNum, DefId, Error, Filename, ImplTraitDecls, ItemKind,
-    Symbol, TraitDecls, mir,
+    BodyAvailability, CodegenAttrs, Crate, CrateItem, CrateItems, CrateNum, DefId, DiagLevel,
+    Error, Filename, ImplTraitDecls, ItemKind, Symbol, TraitDecls, Visibility, mir,
 };
 
 /// This trait defines the interface between stable_mir and the Rust compiler.
 /// Do not use this directly.
 pub trait Context {
     fn entry_fn(&self) -> Option<CrateItem>;
+    /// Retrieve the instance where the execution of the program starts, if any.
+    fn entry_instance(&self) -> Option<Instance>;
     /// Retrieve all items of the local crate that have a MIR associated with them.
     fn all_local_items(&self) -> CrateItems;
     /// Retrieve the body of a function.
@@ -33,6 +36,8 @@ pub trait Context {
     fn mir_body(&self, item: DefId) -> mir::Body;
     /// Check whether the body of a function is available.
     fn has_body(&self, item: DefId) -> bool;
+    /// Check whether the body of a function is available, and if not, why.
+    fn body_availability(&self, item: DefId) -> BodyAvailability;
     fn foreign_modules(&self, crate_num: CrateNum) -> Vec<ForeignModuleDef>;
 
     /// Retrieve all functions defined in this crate.
@@ -48,6 +53,8 @@ pub trait Context {
     fn all_trait_impls(&self) -> ImplTraitDecls;
     fn trait_impls(&self, crate_num: CrateNum) -> ImplTraitDecls;
     fn trait_impl(&self, trait_impl: &ImplDef) -> ImplTrait;
+    /// Evaluate the associated constant with the given name for an implementation.
+    fn impl_associated_const(&self, impl_def: ImplDef, name: &str) -> Result<MirConst, Error>;
     fn generics_of(&self, def_id: DefId) -> Generics;
     fn predicates_of(&self, def_id: DefId) -> GenericPredicates;
     fn explicit_predicates_of(&self, def_id: DefId) -> GenericPredicates;
@@ -80,12 +87,34 @@ pub trait Context {
     /// Return lines corresponding to this `Span`
     fn get_lines(&self, span: &Span) -> LineInfo;
 
+    /// Return the span of the outermost macro call site that expanded into this `Span`, or the
+    /// span itself if it doesn't come from a macro expansion.
+    fn span_source_callsite(&self, span: Span) -> Span;
+
+    /// Return the chain of macro expansions that produced this `Span`, innermost first.
+    fn span_macro_backtrace(&self, span: Span) -> Vec<ExpnInfo>;
+
+    /// Emit a diagnostic with the given level and message at `span`.
+    fn emit_diagnostic(&self, span: Span, level: DiagLevel, message: &str);
+
     /// Returns the `kind` of given `DefId`
     fn item_kind(&self, item: CrateItem) -> ItemKind;
 
     /// Returns whether this is a foreign item.
     fn is_foreign_item(&self, item: DefId) -> bool;
 
+    /// Returns the name of the symbol a foreign item links to.
+    fn foreign_link_name(&self, item: DefId) -> Option<Symbol>;
+
+    /// Returns the code generation attributes of the given item.
+    fn codegen_attrs(&self, item: DefId) -> CodegenAttrs;
+
+    /// Returns the visibility of the given item.
+    fn item_visibility(&self, item: DefId) -> Visibility;
+
+    /// Returns whether the given item is reachable from outside its crate.
+    fn item_is_exported(&self, item: DefId) -> bool;
+
     /// Returns the kind of a given foreign item.
     fn foreign_item_kind(&self, def: ForeignDef) -> ForeignItemKind;
 
@@ -98,6 +127,12 @@ pub trait Context {
     /// Returns whether this ADT is simd.
     fn adt_is_simd(&self, def: AdtDef) -> bool;
 
+    /// Returns whether this ADT has an explicit `Drop` implementation.
+    fn adt_has_drop_impl(&self, def: AdtDef) -> bool;
+
+    /// Returns the type of the non-trivial field of a transparent ADT.
+    fn adt_transparent_field_ty(&self, def: AdtDef, args: &GenericArgs) -> Option<Ty>;
+
     /// Returns whether this definition is a C string.
     fn adt_is_cstr(&self, def: AdtDef) -> bool;
 
@@ -113,6 +148,15 @@ pub trait Context {
     /// Retrieve the closure signature for the given generic arguments.
     fn closure_sig(&self, args: &GenericArgs) -> PolyFnSig;
 
+    /// Retrieve the types of the variables captured by a closure with the given generic arguments.
+    fn closure_upvar_tys(&self, args: &GenericArgs) -> Vec<Ty>;
+
+    /// Retrieve the item where the given closure is defined.
+    fn closure_parent(&self, def: ClosureDef) -> CrateItem;
+
+    /// Retrieve the types held by a coroutine across suspension points.
+    fn coroutine_witness_tys(&self, def: CoroutineWitnessDef, args: &GenericArgs) -> Vec<Ty>;
+
     /// The number of variants in this ADT.
     fn adt_variants_len(&self, def: AdtDef) -> usize;
 
@@ -163,6 +207,15 @@ pub trait Context {
     /// Obtain the representation of a type.
     fn ty_kind(&self, ty: Ty) -> TyKind;
 
+    /// Replace the `Self` type parameter of a trait in the given type.
+    fn ty_replace_self(&self, ty: Ty, self_ty: Ty) -> Ty;
+
+    /// Replace the `Self` type parameter of a trait in the given type constant.
+    fn ty_const_replace_self(&self, ct: &TyConst, self_ty: Ty) -> TyConst;
+
+    /// Replace the `Self` type parameter of a trait in the given MIR constant.
+    fn mir_const_replace_self(&self, cnst: &MirConst, self_ty: Ty) -> MirConst;
+
     // Get the discriminant Ty for this Ty if there's one.
     fn rigid_ty_discriminant_ty(&self, ty: &RigidTy) -> Ty;
 
@@ -175,12 +228,34 @@ pub trait Context {
     /// Get the instantiation types.
     fn instance_args(&self, def: InstanceDef) -> GenericArgs;
 
+    /// Instantiate a type from the body of the instance item with the instance arguments, and
+    /// normalize it.
+    fn instance_instantiate_ty(&self, def: InstanceDef, ty: Ty) -> Result<Ty, Error>;
+
     /// Get the instance.
     fn instance_def_id(&self, instance: InstanceDef) -> DefId;
 
     /// Get the instance mangled name.
     fn instance_mangled_name(&self, instance: InstanceDef) -> Symbol;
 
+    /// Collect and partition the mono items of the local crate into codegen units.
+    fn codegen_units(&self) -> Vec<CodegenUnit>;
+
+    /// Check whether the local crate is compiled with overflow checks.
+    fn overflow_checks_enabled(&self) -> bool;
+
+    /// Check whether the local crate is compiled with debug assertions.
+    fn debug_assertions_enabled(&self) -> bool;
+
+    /// Get the upstream crate where the instance is already monomorphized, if any.
+    fn upstream_monomorphization(&self, instance: InstanceDef) -> Option<Crate>;
+
+    /// Check if this is a DropGlue shim.
+    fn is_drop_glue(&self, def: InstanceDef) -> bool;
+
+    /// Check if the instance takes the caller location as an implicit argument.
+    fn instance_requires_caller_location(&self, def: InstanceDef) -> bool;
+
     /// Check if this is an empty DropGlue shim.
     fn is_empty_drop_shim(&self, def: InstanceDef) -> bool;
 
@@ -211,17 +286,40 @@ fn resolve_closure(
         kind: ClosureKind,
     ) -> Option<Instance>;
 
+    /// Retrieve the body that computes the value of a constant or static, if it is available.
+    fn initializer_body(&self, def: DefId) -> Option<Body>;
+
     /// Evaluate a static's initializer.
     fn eval_static_initializer(&self, def: StaticDef) -> Result<Allocation, Error>;
 
     /// Try to evaluate an instance into a constant.
     fn eval_instance(&self, def: InstanceDef, const_ty: Ty) -> Result<Allocation, Error>;
 
+    /// Evaluate an unevaluated constant after instantiating its generic arguments with `args`.
+    fn eval_unevaluated_const(
+        &self,
+        cnst: &UnevaluatedConst,
+        args: &GenericArgs,
+        reveal: Reveal,
+    ) -> Result<MirConst, Error>;
+
     /// Retrieve global allocation for the given allocation ID.
     fn global_alloc(&self, id: AllocId) -> GlobalAlloc;
 
     /// Retrieve the id for the virtual table.
     fn vtable_allocation(&self, global_alloc: &GlobalAlloc) -> Option<AllocId>;
+
+    /// Find the slot of the supertrait vtable pointer when upcasting `source` to `target`.
+    fn supertrait_vtable_slot(&self, source: Ty, target: Ty) -> Result<Option<usize>, Error>;
+
+    /// Check whether two allocation IDs refer to the same interned global allocation.
+    fn same_global_alloc(&self, lhs: AllocId, rhs: AllocId) -> bool;
+
+    /// Check whether an allocation ID with the given index exists in this compilation.
+    fn alloc_id_exists(&self, index: usize) -> bool;
+
+    /// Check whether a definition ID with the given index exists in this compilation.
+    fn def_id_exists(&self, index: usize) -> bool;
     fn krate(&self, def_id: DefId) -> Crate;
     fn instance_name(&self, def: InstanceDef, trimmed: bool) -> Symbol;
 
@@ -234,9 +332,18 @@ fn resolve_closure(
     /// Get the ABI of a function pointer.
     fn fn_ptr_abi(&self, fn_ptr: PolyFnSig) -> Result<FnAbi, Error>;
 
+    /// Normalize a type, and reveal its opaque types depending on the given mode.
+    fn normalize_ty(&self, ty: Ty, reveal: Reveal) -> Result<Ty, Error>;
+
     /// Get the layout of a type.
     fn ty_layout(&self, ty: Ty) -> Result<Layout, Error>;
 
+    /// Check whether a type is uninhabited, ignoring the visibility of its fields.
+    fn ty_is_uninhabited(&self, ty: Ty) -> bool;
+
+    /// Get the discriminant value of every variant of an enum type.
+    fn ty_discriminants(&self, ty: Ty) -> Option<Vec<(VariantIdx, u128)>>;
+
     /// Get the layout shape.
     fn layout_shape(&self, id: Layout) -> LayoutShape;
 
@@ -7,9 +7,11 @@
 use std::fmt::{Debug, Display, Formatter};
 use std::{fmt, io};
 
+use crate::ty::Span;
+
 macro_rules! error {
-     ($fmt: literal $(,)?) => { Error(format!($fmt)) };
-     ($fmt: literal, $($arg:tt)*) => { Error(format!($fmt, $($arg)*)) };
+     ($fmt: literal $(,)?) => { Error::new(format!($fmt)) };
+     ($fmt: literal, $($arg:tt)*) => { Error::new(format!($fmt, $($arg)*)) };
 }
 
 pub(crate) use error;
@@ -28,23 +30,36 @@ pub enum CompilerError<T> {
 
 /// A generic error to represent an API request that cannot be fulfilled.
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct Error(pub(crate) String);
+pub struct Error {
+    msg: String,
+    span: Option<Span>,
+}
 
 impl Error {
     pub fn new(msg: String) -> Self {
-        Self(msg)
+        Self { msg, span: None }
+    }
+
+    /// Create an error that refers to the given location in the source code.
+    pub fn with_span(msg: String, span: Span) -> Self {
+        Self { msg, span: Some(span) }
+    }
+
+    /// The location in the source code that caused this error, if any.
+    pub fn span(&self) -> Option<Span> {
+        self.span
     }
 }
 
 impl From<&str> for Error {
     fn from(value: &str) -> Self {
-        Self(value.into())
+        Self::new(value.into())
     }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        Display::fmt(&self.0, f)
+        Display::fmt(&self.msg, f)
     }
 }
 
@@ -80,6 +95,6 @@ impl<T> std::error::Error for CompilerError<T> where T: Display + Debug {}
 
 impl From<io::Error> for Error {
     fn from(value: io::Error) -> Self {
-        Error(value.to_string())
+        Error::new(value.to_string())
     }
 }
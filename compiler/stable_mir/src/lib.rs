@@ -17,6 +17,7 @@
 //! The goal is to eventually be published on
 //! [crates.io](https://crates.io).
 
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::{fmt, io};
 
@@ -25,8 +26,8 @@
 use crate::compiler_interface::with;
 pub use crate::crate_def::{CrateDef, CrateDefType, DefId};
 pub use crate::error::*;
-use crate::mir::mono::StaticDef;
-use crate::mir::{Body, Mutability};
+use crate::mir::mono::{CodegenUnit, Instance, MonoItem, StaticDef};
+use crate::mir::{Body, MirVisitor, Mutability};
 use crate::ty::{FnDef, ForeignModuleDef, ImplDef, IndexedVal, Span, TraitDef, Ty};
 
 pub mod abi;
@@ -55,6 +56,35 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+impl DefId {
+    /// Return the fully qualified name of this definition.
+    ///
+    /// This is the same as [CrateDef::name] for the definition with this ID.
+    pub fn name(&self) -> Symbol {
+        with(|cx| cx.def_name(*self, false))
+    }
+
+    /// Return a trimmed name of this definition.
+    ///
+    /// This is the same as [CrateDef::trimmed_name] for the definition with this ID.
+    pub fn trimmed_name(&self) -> Symbol {
+        with(|cx| cx.def_name(*self, true))
+    }
+
+    /// Create a definition ID from its index, checking that it exists in the current
+    /// compilation.
+    ///
+    /// This should be used instead of [IndexedVal::to_val] for indices that come from external
+    /// data, since invalid IDs cannot be used to query the compiler.
+    pub fn from_index_checked(index: usize) -> Result<DefId, Error> {
+        if with(|cx| cx.def_id_exists(index)) {
+            Ok(DefId(index))
+        } else {
+            Err(Error::new(format!("Unknown definition ID: `{index}`")))
+        }
+    }
+}
+
 impl IndexedVal for DefId {
     fn to_val(index: usize) -> Self {
         DefId(index)
@@ -123,6 +153,65 @@ pub enum CtorKind {
     Fn,
 }
 
+/// The visibility of an item.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Serialize)]
+pub enum Visibility {
+    /// Visible everywhere, i.e., `pub`.
+    Public,
+    /// Visible only inside the given module, e.g., `pub(crate)` or `pub(super)`.
+    Restricted(DefId),
+    /// Visible only inside the module where the item is declared.
+    Private,
+}
+
+/// Attributes of an item that are interpreted by the compiler to control code generation.
+#[derive(Clone, PartialEq, Eq, Debug, Default, Hash, Serialize)]
+pub struct CodegenAttrs {
+    /// Whether the function is `#[naked]`, i.e., it has no prologue or epilogue.
+    pub naked: bool,
+    /// Whether the item is `#[no_mangle]`.
+    pub no_mangle: bool,
+    /// The symbol name given by `#[export_name = "..."]`, if any.
+    pub export_name: Option<Symbol>,
+    /// The inlining hint given by `#[inline]`, if any.
+    pub inline: InlineAttr,
+    /// The target features enabled by `#[target_feature(enable = "...")]`, including the
+    /// features that they imply.
+    pub target_features: Vec<Symbol>,
+}
+
+/// The inlining hint of a function, as given by the `#[inline]` attribute.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Hash, Serialize)]
+pub enum InlineAttr {
+    /// The function has no inlining hint.
+    #[default]
+    None,
+    /// `#[inline]`
+    Hint,
+    /// `#[inline(always)]`
+    Always,
+    /// `#[inline(never)]`
+    Never,
+    /// `#[rustc_force_inline]`, which requires the function to be inlined by the MIR inliner.
+    Force,
+}
+
+/// Whether the MIR body of an item is available, and if not, why.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Serialize)]
+pub enum BodyAvailability {
+    /// The body is available, and can be retrieved with [CrateItem::body].
+    Available,
+    /// The item is declared in an `extern` block, so it is defined outside of Rust.
+    Foreign,
+    /// The item has no body in the current compilation.
+    ///
+    /// This is the case for functions from other crates that are not generic nor `#[inline]`,
+    /// since their MIR is not encoded in the crate metadata.
+    NotEncoded,
+    /// The item is an intrinsic that must be implemented by the compiler backend.
+    Intrinsic,
+}
+
 pub type Filename = String;
 
 crate_def_with_ty! {
@@ -139,6 +228,11 @@ pub fn body(&self) -> mir::Body {
         with(|cx| cx.mir_body(self.0))
     }
 
+    /// Check whether the body of this item is available, and if not, why.
+    pub fn body_availability(&self) -> BodyAvailability {
+        with(|cx| cx.body_availability(self.0))
+    }
+
     pub fn span(&self) -> Span {
         with(|cx| cx.span_of_an_item(self.0))
     }
@@ -159,6 +253,52 @@ pub fn is_foreign_item(&self) -> bool {
         with(|cx| cx.is_foreign_item(self.0))
     }
 
+    /// The name of the symbol that this foreign item links to.
+    ///
+    /// This is the name given by `#[link_name]` if present, or the item name otherwise.
+    /// Returns `None` if this is not a foreign item.
+    pub fn foreign_link_name(&self) -> Option<Symbol> {
+        with(|cx| cx.foreign_link_name(self.0))
+    }
+
+    /// The attributes of this item that control how the compiler generates code for it.
+    ///
+    /// Items that do not support such attributes return the default value.
+    pub fn codegen_attrs(&self) -> CodegenAttrs {
+        with(|cx| cx.codegen_attrs(self.0))
+    }
+
+    /// The inlining hint of this item, as interpreted by the compiler.
+    ///
+    /// Items that are not functions have no inlining hint.
+    pub fn inline_attr(&self) -> InlineAttr {
+        self.codegen_attrs().inline
+    }
+
+    /// The target features enabled for this function by `#[target_feature]`.
+    ///
+    /// This includes the features implied by the ones that are listed explicitly, e.g., `avx`
+    /// for `avx2`, but not the features that are enabled for the whole crate.
+    pub fn target_features(&self) -> Vec<Symbol> {
+        self.codegen_attrs().target_features
+    }
+
+    /// The visibility of this item, as declared in the source code.
+    ///
+    /// Items that don't have a visibility of their own, such as closures and anonymous
+    /// constants, have the visibility of the item that contains them.
+    pub fn visibility(&self) -> Visibility {
+        with(|cx| cx.item_visibility(self.0))
+    }
+
+    /// Whether this item can be reached from outside its crate.
+    ///
+    /// This takes into account the visibility of the modules that contain the item, and any
+    /// re-export of the item.
+    pub fn is_exported(&self) -> bool {
+        with(|cx| cx.item_is_exported(self.0))
+    }
+
     pub fn emit_mir<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
         self.body().dump(w, &self.name())
     }
@@ -171,6 +311,67 @@ pub fn entry_fn() -> Option<CrateItem> {
     with(|cx| cx.entry_fn())
 }
 
+/// Return the instance where the execution of the program starts if the current crate
+/// defines an entry function.
+///
+/// For a `main` function, this is the instance of the `start` lang item that calls `main`,
+/// such as `std::rt::lang_start::<()>`. Otherwise, this is the instance of the entry function
+/// itself.
+pub fn entry_instance() -> Option<Instance> {
+    with(|cx| cx.entry_instance())
+}
+
+/// Return the upstream crate that already contains the code generated for the given instance.
+///
+/// The current crate can link to this instance instead of generating its own copy. This
+/// returns `None` if the instance must be generated locally, e.g., if it is not generic, or if
+/// upstream crates were not compiled with shared generics.
+pub fn upstream_monomorphizations(instance: &Instance) -> Option<Crate> {
+    with(|cx| cx.upstream_monomorphization(instance.def))
+}
+
+/// Retrieve the codegen units of the local crate, and the items that are generated in each of
+/// them.
+///
+/// This triggers the collection and partitioning of all the items that need to be generated
+/// for the local crate, which is expensive for large crates.
+pub fn codegen_units() -> Vec<CodegenUnit> {
+    with(|cx| cx.codegen_units())
+}
+
+/// Run the given visitor over the body of every function instance generated for the local crate.
+///
+/// The instances are collected from the [codegen_units], and each one is visited only once, even
+/// if it is generated in multiple codegen units. Instances without a body are skipped.
+#[allow(rustc::default_hash_types)]
+pub fn visit_all_mono_bodies(visitor: &mut impl MirVisitor) {
+    let mut visited = HashSet::new();
+    for item in codegen_units().into_iter().flat_map(|unit| unit.items) {
+        let MonoItem::Fn(instance) = item else { continue };
+        if visited.insert(instance) {
+            if let Some(body) = instance.body() {
+                visitor.visit_body(&body);
+            }
+        }
+    }
+}
+
+/// Check whether the local crate is compiled with overflow checks, e.g., with
+/// `-Coverflow-checks`, or by default in builds with debug assertions.
+///
+/// When enabled, arithmetic operations that may overflow are followed by an `Assert` terminator
+/// with an [AssertMessage::Overflow](mir::AssertMessage::Overflow). Note that the bodies of items
+/// from other crates were built with the settings of their own crate.
+pub fn overflow_checks_enabled() -> bool {
+    with(|cx| cx.overflow_checks_enabled())
+}
+
+/// Check whether the local crate is compiled with debug assertions, i.e., whether
+/// `cfg(debug_assertions)` is enabled.
+pub fn debug_assertions_enabled() -> bool {
+    with(|cx| cx.debug_assertions_enabled())
+}
+
 /// Access to the local crate.
 pub fn local_crate() -> Crate {
     with(|cx| cx.local_crate())
@@ -191,6 +392,17 @@ pub fn all_local_items() -> CrateItems {
     with(|cx| cx.all_local_items())
 }
 
+/// Retrieve the bodies of the given items.
+///
+/// This is a convenience wrapper equivalent to calling [CrateItem::body] for each item. The
+/// bodies are built the same way, so the only saving is that the compiler context is retrieved
+/// once for all items, instead of once per item.
+///
+/// This will panic if the body of any of the items is not available.
+pub fn mir_bodies(items: &[CrateItem]) -> Vec<Body> {
+    with(|cx| items.iter().map(|item| cx.mir_body(item.0)).collect())
+}
+
 pub fn all_trait_decls() -> TraitDecls {
     with(|cx| cx.all_trait_decls())
 }
@@ -199,6 +411,40 @@ pub fn all_trait_impls() -> ImplTraitDecls {
     with(|cx| cx.all_trait_impls())
 }
 
+/// Retrieve all trait implementations grouped by the trait they implement.
+///
+/// This is computed from [all_trait_impls], and it is meant to be built once and reused when
+/// looking up the implementations of many traits.
+#[allow(rustc::default_hash_types)]
+pub fn trait_impl_index() -> HashMap<TraitDef, ImplTraitDecls> {
+    with(|cx| {
+        let mut index: HashMap<TraitDef, ImplTraitDecls> = HashMap::new();
+        for impl_def in cx.all_trait_impls() {
+            index.entry(cx.trait_impl(&impl_def).value.def_id).or_default().push(impl_def);
+        }
+        index
+    })
+}
+
+/// The severity of a diagnostic emitted with [emit_diagnostic].
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Serialize)]
+pub enum DiagLevel {
+    /// An error, which will cause the compilation to fail.
+    Error,
+    /// A warning, which is reported without affecting the result of the compilation.
+    Warning,
+    /// A note that gives the user additional information, without indicating a problem.
+    Note,
+}
+
+/// Emit a diagnostic at the given span, which is reported like any other compiler diagnostic.
+///
+/// This allows tools built on top of this crate, such as linters, to report issues in the
+/// source code of the crate being compiled.
+pub fn emit_diagnostic(span: Span, level: DiagLevel, message: &str) {
+    with(|cx| cx.emit_diagnostic(span, level, message))
+}
+
 /// A type that provides internal information but that can still be used for debug purpose.
 #[derive(Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct Opaque(String);
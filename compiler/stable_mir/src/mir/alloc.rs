@@ -1,16 +1,23 @@
 //! This module provides methods to retrieve allocation information, such as static variables.
 
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::io::Read;
 
 use serde::Serialize;
 
 use crate::mir::mono::{Instance, StaticDef};
 use crate::target::{Endian, MachineInfo};
-use crate::ty::{Allocation, Binder, ExistentialTraitRef, IndexedVal, Ty};
-use crate::{Error, with};
+use crate::ty::{Allocation, Binder, ExistentialTraitRef, GenericArgKind, IndexedVal, Ty};
+use crate::{CrateDef, Error, with};
 
 /// An allocation in the SMIR global memory can be either a function pointer,
 /// a static, or a "real" allocation with some data in it.
+///
+/// Note that the structural equality implemented for this type compares the full content of
+/// [GlobalAlloc::Memory], which can be expensive for big allocations. This is appropriate when
+/// comparing allocations that were built or modified by the user. In order to check whether two
+/// allocation IDs point to the same allocation, use [AllocId::same_allocation] instead.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 pub enum GlobalAlloc {
     /// The alloc ID is used as a function pointer.
@@ -41,12 +48,145 @@ impl GlobalAlloc {
     pub fn vtable_allocation(&self) -> Option<AllocId> {
         with(|cx| cx.vtable_allocation(self))
     }
+
+    /// Retrieve the memory of this allocation if it is a [GlobalAlloc::Memory].
+    pub fn as_memory(&self) -> Option<&Allocation> {
+        if let GlobalAlloc::Memory(alloc) = self { Some(alloc) } else { None }
+    }
+
+    /// Retrieve the static definition if this is a [GlobalAlloc::Static].
+    pub fn as_static(&self) -> Option<StaticDef> {
+        if let GlobalAlloc::Static(def) = self { Some(*def) } else { None }
+    }
+
+    /// Retrieve the function instance if this is a [GlobalAlloc::Function].
+    pub fn as_function(&self) -> Option<&Instance> {
+        if let GlobalAlloc::Function(instance) = self { Some(instance) } else { None }
+    }
 }
 
 /// A unique identification number for each provenance
+///
+/// Allocation IDs are assigned in the order that the compiler interns the allocations, which
+/// depends on the order of the queries made during compilation. Thus, the same allocation may get
+/// a different ID in two runs of the compiler over the same crate. This is why this type doesn't
+/// implement `Ord`. Use [AllocId::stable_key] to build ordered collections of allocations.
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize)]
 pub struct AllocId(usize);
 
+impl AllocId {
+    /// Check whether this allocation ID and `other` point to the same global allocation.
+    ///
+    /// This is much cheaper than comparing the result of `GlobalAlloc::from` for both IDs, since
+    /// the compiler interns its allocations, and the comparison doesn't require inspecting the
+    /// allocation bytes.
+    ///
+    /// Note that distinct allocation IDs may still refer to the same allocation.
+    pub fn same_allocation(&self, other: &AllocId) -> bool {
+        self == other || with(|cx| cx.same_global_alloc(*self, *other))
+    }
+
+    /// Create an allocation ID from its index, checking that it exists in the current
+    /// compilation.
+    ///
+    /// This should be used instead of [IndexedVal::to_val] for indices that come from external
+    /// data, such as a serialized body, since invalid IDs cannot be used to query the compiler.
+    pub fn from_index_checked(index: usize) -> Result<AllocId, Error> {
+        if with(|cx| cx.alloc_id_exists(index)) {
+            Ok(AllocId(index))
+        } else {
+            Err(Error::new(format!("Unknown allocation ID: `{index}`")))
+        }
+    }
+
+    /// Compute a key for this allocation that is derived from its content.
+    ///
+    /// Unlike the allocation ID itself, this key doesn't depend on the order in which
+    /// allocations were created, so it can be used as the key of a `BTreeMap` that should produce
+    /// the same order across different runs of the compiler.
+    ///
+    /// Functions and statics are identified by their mangled names, and vtables by their type and
+    /// trait. Memory allocations are identified by their alignment, mutability and size, and by a
+    /// digest of their bytes, including which ones are uninitialized, and of the offset and key of
+    /// each pointer they contain. Thus, two memory allocations with the same content have the same
+    /// key, even if they have different IDs, and the size of the key doesn't depend on the size of
+    /// the allocation.
+    pub fn stable_key(&self) -> AllocKey {
+        self.stable_key_with(&mut HashMap::new())
+    }
+
+    /// Compute the key of this allocation, reusing the keys in `memo` for the allocations that
+    /// were already visited, since several pointers may point to the same allocation.
+    fn stable_key_with(&self, memo: &mut HashMap<AllocId, AllocKey>) -> AllocKey {
+        if let Some(key) = memo.get(self) {
+            return key.clone();
+        }
+        let key = match GlobalAlloc::from(*self) {
+            GlobalAlloc::Function(instance) => format!("fn {}", instance.mangled_name()),
+            GlobalAlloc::Static(def) => format!("static {}", Instance::from(def).mangled_name()),
+            GlobalAlloc::VTable(ty, trait_ref) => vtable_key(ty, trait_ref),
+            GlobalAlloc::Memory(alloc) => memory_key(&alloc, memo),
+        };
+        let key = AllocKey(key);
+        memo.insert(*self, key.clone());
+        key
+    }
+}
+
+/// A key that identifies an allocation by its content. See [AllocId::stable_key].
+///
+/// Keys are ordered by their textual representation.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize)]
+pub struct AllocKey(String);
+
+impl AllocKey {
+    /// The textual representation of this key.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+fn memory_key(alloc: &Allocation, memo: &mut HashMap<AllocId, AllocKey>) -> String {
+    // Hashers created with `DefaultHasher::new` use the same keys, so the digest is the same
+    // across different runs of the compiler.
+    let mut hasher = DefaultHasher::new();
+    alloc.bytes.hash(&mut hasher);
+    for (offset, prov) in &alloc.provenance.ptrs {
+        offset.hash(&mut hasher);
+        prov.0.stable_key_with(memo).hash(&mut hasher);
+    }
+    format!(
+        "memory align {} {:?} size {} {:016x}",
+        alloc.align.bytes(),
+        alloc.mutability,
+        alloc.bytes.len(),
+        hasher.finish()
+    )
+}
+
+fn vtable_key(ty: Ty, trait_ref: Option<Binder<ExistentialTraitRef>>) -> String {
+    let Some(trait_ref) = trait_ref else {
+        return format!("vtable {ty}");
+    };
+    let args = trait_ref
+        .value
+        .generic_args
+        .0
+        .iter()
+        .map(|arg| match arg {
+            GenericArgKind::Lifetime(_) => "'_".to_string(),
+            GenericArgKind::Type(ty) => ty.to_string(),
+            GenericArgKind::Const(ct) => with(|cx| cx.ty_const_pretty(ct.id)),
+        })
+        .collect::<Vec<_>>();
+    let name = trait_ref.value.def_id.name();
+    if args.is_empty() {
+        format!("vtable {ty} as {name}")
+    } else {
+        format!("vtable {ty} as {name}<{}>", args.join(", "))
+    }
+}
+
 impl IndexedVal for AllocId {
     fn to_val(index: usize) -> Self {
         AllocId(index)
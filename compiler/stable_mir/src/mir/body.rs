@@ -1,13 +1,17 @@
-use std::io;
+use std::ops::ControlFlow;
+use std::{io, iter};
 
 use serde::Serialize;
 
 use crate::compiler_interface::with;
-use crate::mir::pretty::function_body;
+use crate::mir::mono::Instance;
+use crate::mir::pretty::{function_body, function_body_dot, pretty_debug_info_value};
+use crate::mir::visit::{LocalUsage, LocalUsageCollector, MirVisitor};
 use crate::ty::{
-    AdtDef, ClosureDef, CoroutineClosureDef, CoroutineDef, GenericArgs, MirConst, Movability,
-    Region, RigidTy, Ty, TyConst, TyKind, VariantIdx,
+    AdtDef, ClosureDef, CoroutineClosureDef, CoroutineDef, GenericArgKind, GenericArgs, IndexedVal,
+    MirConst, Movability, Region, RigidTy, Ty, TyConst, TyConstKind, TyKind, VariantIdx,
 };
+use crate::visitor::{Visitable, Visitor};
 use crate::{Error, Opaque, Span, Symbol};
 
 /// The SMIR representation of a single function.
@@ -35,6 +39,12 @@ pub struct Body {
 
     /// The span that covers the entire function body.
     pub span: Span,
+
+    /// The layout of the coroutine state, if this is the body of a coroutine.
+    pub(super) coroutine_layout: Option<CoroutineLayout>,
+
+    /// The lexical scopes of this body, indexed by [SourceScope].
+    pub(super) source_scopes: Vec<SourceScopeData>,
 }
 
 pub type BasicBlockIdx = usize;
@@ -58,7 +68,41 @@ pub fn new(
             locals.len() > arg_count,
             "A Body must contain at least a local for the return value and each of the function's arguments"
         );
-        Self { blocks, locals, arg_count, var_debug_info, spread_arg, span }
+        Self {
+            blocks,
+            locals,
+            arg_count,
+            var_debug_info,
+            spread_arg,
+            span,
+            coroutine_layout: None,
+            source_scopes: Vec::new(),
+        }
+    }
+
+    /// Attach the layout of the coroutine state to a coroutine body.
+    ///
+    /// The layout must describe the coroutine that this body implements, and this body must take
+    /// the coroutine and its resume argument as arguments.
+    ///
+    /// Returns an error if the resume local of the layout is not one of the body arguments.
+    pub fn with_coroutine_layout(mut self, layout: CoroutineLayout) -> Result<Self, Error> {
+        if let Some(resume_local) = layout.resume_local {
+            if resume_local == RETURN_LOCAL || resume_local > self.arg_count {
+                return Err(error!(
+                    "The resume local `{resume_local}` of a coroutine must be one of the body \
+                     arguments"
+                ));
+            }
+        }
+        self.coroutine_layout = Some(layout);
+        Ok(self)
+    }
+
+    /// Attach the lexical scopes of this body, indexed by [SourceScope].
+    pub fn with_source_scopes(mut self, source_scopes: Vec<SourceScopeData>) -> Self {
+        self.source_scopes = source_scopes;
+        self
     }
 
     /// Return local that holds this function's return value.
@@ -85,11 +129,35 @@ pub fn locals(&self) -> &[LocalDecl] {
         &self.locals
     }
 
+    /// The types of all the locals in this function, indexed by [Local].
+    ///
+    /// The first type is always the type of the return local.
+    pub fn local_types(&self) -> Vec<Ty> {
+        self.locals.iter().map(|decl| decl.ty).collect()
+    }
+
     /// Get the local declaration for this local.
     pub fn local_decl(&self, local: Local) -> Option<&LocalDecl> {
         self.locals.get(local)
     }
 
+    /// Classify the given local as the return local, an argument, a user variable or a temporary.
+    ///
+    /// Locals that are not arguments are considered user variables if they have debug
+    /// information attached to them. This will panic if the local doesn't exist in this body.
+    pub fn local_kind(&self, local: Local) -> LocalKind {
+        assert!(local < self.locals.len(), "Local `{local}` doesn't exist in this body");
+        if local == RETURN_LOCAL {
+            LocalKind::ReturnPointer
+        } else if local <= self.arg_count {
+            LocalKind::Arg
+        } else if self.var_debug_info.iter().any(|info| info.local() == Some(local)) {
+            LocalKind::Var
+        } else {
+            LocalKind::Temp
+        }
+    }
+
     /// Get an iterator for all local declarations.
     pub fn local_decls(&self) -> impl Iterator<Item = (Local, &LocalDecl)> {
         self.locals.iter().enumerate()
@@ -100,13 +168,540 @@ pub fn dump<W: io::Write>(&self, w: &mut W, fn_name: &str) -> io::Result<()> {
         function_body(w, self, fn_name)
     }
 
+    /// Produce a copy of this body with a canonical order of its debug information.
+    ///
+    /// The entries of [Body::var_debug_info] are sorted by their name, argument index, the
+    /// source location of their span, their scope, their value, and their fragment, which
+    /// doesn't depend on the order that the compiler produced them. Since every field takes part
+    /// in the order, only entries that are identical keep their relative order. This is useful
+    /// to compare dumps of equivalent bodies.
+    pub fn canonicalize(&self) -> Body {
+        let mut body = self.clone();
+        body.var_debug_info.sort_by_cached_key(|info| {
+            let span = info.source_info.span;
+            let lines = span.get_lines();
+            let composite = info.composite.as_ref().map(|fragment| {
+                // Render the projections on top of a placeholder local, since they are printed
+                // as part of a place.
+                let place = Place { local: RETURN_LOCAL, projection: fragment.projection.clone() };
+                (fragment.ty.to_string(), format!("{place:?}"))
+            });
+            (
+                info.name.clone(),
+                info.argument_index,
+                span.get_filename(),
+                (lines.start_line, lines.start_col, lines.end_line, lines.end_col),
+                info.source_info.scope,
+                pretty_debug_info_value(&info.value),
+                composite,
+            )
+        });
+        body
+    }
+
+    /// Replace the `Self` type parameter by the given type in the types and constants of this
+    /// body.
+    ///
+    /// This is useful to analyze the default body of a trait method for a type that doesn't
+    /// override it. `Self` is the type parameter with index `0` in a trait and its associated
+    /// items. Other generic parameters are kept as is.
+    ///
+    /// Returns an error if the given type is generic.
+    pub fn substitute_self(&self, ty: Ty) -> Result<Body, Error> {
+        if ty.visit(&mut GenericFinder).is_break() {
+            return Err(error!("Expected a type without generic parameters, but found `{ty}`"));
+        }
+        let mut body = self.clone();
+        SelfReplacer { self_ty: ty }.replace_body(&mut body);
+        Ok(body)
+    }
+
+    /// Emit the control-flow graph of this body in the Graphviz DOT format.
+    ///
+    /// There is one node per basic block, labeled with its statements and terminator, and one
+    /// edge per successor. Unwind edges are dashed.
+    pub fn to_dot(&self, name: &str) -> String {
+        let mut buf = Vec::new();
+        function_body_dot(&mut buf, self, name).expect("writing to a buffer cannot fail");
+        String::from_utf8(buf).expect("DOT output should be valid UTF-8")
+    }
+
+    /// The argument local that is passed as its individual components, if any.
+    ///
+    /// This is only the case for functions using the "rust-call" ABI, such as the `call` methods
+    /// of the `Fn*` traits, where the last argument is a tuple that callers pass untupled.
     pub fn spread_arg(&self) -> Option<Local> {
         self.spread_arg
     }
+
+    /// The types of the arguments of this function as seen by its callers.
+    ///
+    /// This is the type of each argument local, except that the tuple in the [spread
+    /// argument](Self::spread_arg) is replaced by the types of its fields.
+    pub fn expanded_arg_tys(&self) -> Vec<Ty> {
+        let mut tys = Vec::with_capacity(self.arg_count);
+        for (idx, decl) in self.arg_locals().iter().enumerate() {
+            match decl.ty.kind() {
+                TyKind::RigidTy(RigidTy::Tuple(fields)) if self.spread_arg == Some(idx + 1) => {
+                    tys.extend(fields)
+                }
+                _ => tys.push(decl.ty),
+            }
+        }
+        tys
+    }
+
+    /// The layout of the coroutine state, including its variants and its resume local.
+    ///
+    /// This is only available for the bodies of coroutines, such as the ones created
+    /// for `async` functions and blocks. It returns `None` for any other body.
+    pub fn coroutine_layout(&self) -> Option<&CoroutineLayout> {
+        self.coroutine_layout.as_ref()
+    }
+
+    /// The lexical scopes of this body, indexed by [SourceScope].
+    ///
+    /// This is empty if no scope was attached to a body built with [Body::new].
+    pub fn source_scopes(&self) -> &[SourceScopeData] {
+        &self.source_scopes
+    }
+
+    /// The stack of inlined calls that contain the given scope.
+    ///
+    /// Each entry is an instance whose body was inlined into this one, together with the span of
+    /// the call that was inlined. The innermost call comes first. This is empty if the scope does
+    /// not come from an inlined function, or if it is not a scope of this body.
+    pub fn inlined_at(&self, scope: SourceScope) -> Vec<(Instance, Span)> {
+        let mut calls = Vec::new();
+        let mut next = self.source_scopes.get(scope as usize);
+        while let Some(data) = next {
+            calls.extend(data.inlined);
+            next = data.parent_scope.and_then(|parent| self.source_scopes.get(parent as usize));
+        }
+        calls
+    }
+
+    /// Collect the usages of every local in this body, indexed by [Local].
+    ///
+    /// Use this to check, for example, whether a local is ever mutated.
+    pub fn local_usages(&self) -> Vec<LocalUsage> {
+        let mut collector =
+            LocalUsageCollector { usages: vec![LocalUsage::default(); self.locals.len()] };
+        collector.visit_body(self);
+        collector.usages
+    }
+
+    /// The block where the execution of this body starts, which is always the first block.
+    pub fn entry_block(&self) -> BasicBlockIdx {
+        0
+    }
+
+    /// The blocks that return from this function, i.e., whose terminator is `Return`.
+    ///
+    /// A body may have multiple return blocks. Analyses that require a single exit can connect
+    /// these blocks to a virtual exit node.
+    pub fn return_blocks(&self) -> Vec<BasicBlockIdx> {
+        self.blocks_with_terminator(|kind| matches!(kind, TerminatorKind::Return))
+    }
+
+    /// The blocks that resume unwinding from this function, i.e., whose terminator is `Resume`.
+    pub fn resume_blocks(&self) -> Vec<BasicBlockIdx> {
+        self.blocks_with_terminator(|kind| matches!(kind, TerminatorKind::Resume))
+    }
+
+    /// Iterate over all the statements in this body, together with their location.
+    pub fn statements(&self) -> impl Iterator<Item = (MirLocation, &Statement)> {
+        self.blocks.iter().enumerate().flat_map(|(block, bb)| {
+            bb.statements
+                .iter()
+                .enumerate()
+                .map(move |(statement_index, stmt)| (MirLocation { block, statement_index }, stmt))
+        })
+    }
+
+    /// Iterate over the terminators of all blocks in this body, together with their location.
+    pub fn terminators(&self) -> impl Iterator<Item = (MirLocation, &Terminator)> {
+        self.blocks.iter().enumerate().map(|(block, bb)| {
+            (MirLocation { block, statement_index: bb.statements.len() }, &bb.terminator)
+        })
+    }
+
+    /// Insert a statement at the given location, shifting the following statements of the block.
+    ///
+    /// The statement index may be equal to the number of statements in the block, in which case
+    /// the statement is inserted right before the terminator. Block indices are not affected.
+    ///
+    /// Returns an error if the location doesn't exist in this body.
+    pub fn insert_statement(&mut self, loc: MirLocation, stmt: Statement) -> Result<(), Error> {
+        let block = self
+            .blocks
+            .get_mut(loc.block)
+            .ok_or_else(|| error!("Invalid basic block: `{}`", loc.block))?;
+        if loc.statement_index > block.statements.len() {
+            return Err(error!("Invalid statement index: `{}`", loc.statement_index));
+        }
+        block.statements.insert(loc.statement_index, stmt);
+        Ok(())
+    }
+
+    /// Replace the terminator of the given block, returning the previous one.
+    ///
+    /// Returns an error if the block doesn't exist, or if the new terminator jumps to a block that
+    /// doesn't exist in this body. In that case, the body is left unchanged.
+    pub fn replace_terminator(
+        &mut self,
+        bb: BasicBlockIdx,
+        term: Terminator,
+    ) -> Result<Terminator, Error> {
+        let num_blocks = self.blocks.len();
+        if let Some(target) = term.successors().into_iter().find(|target| *target >= num_blocks) {
+            return Err(error!("Invalid successor block: `{target}`"));
+        }
+        let block = self.blocks.get_mut(bb).ok_or_else(|| error!("Invalid basic block: `{bb}`"))?;
+        Ok(std::mem::replace(&mut block.terminator, term))
+    }
+
+    /// Map every statement and terminator in this body to the line where its source code starts.
+    ///
+    /// Code that comes from a macro expansion is mapped to the line of the macro call.
+    pub fn line_map(&self) -> Vec<(MirLocation, usize)> {
+        self.blocks
+            .iter()
+            .enumerate()
+            .flat_map(|(block, bb)| {
+                let spans = bb.statements.iter().map(|stmt| stmt.span);
+                spans.chain(iter::once(bb.terminator.span)).enumerate().map(
+                    move |(statement_index, span)| {
+                        let line = span.source_callsite().get_lines().start_line;
+                        (MirLocation { block, statement_index }, line)
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Resolve the drop glue instance for the place dropped by the given `Drop` terminator.
+    ///
+    /// This body must be the body of the given `instance`, either the generic body of its item or
+    /// its monomorphized body, and the terminator must belong to this body. The type of the dropped
+    /// place is instantiated with the generic arguments of `instance`.
+    ///
+    /// Returns `None` if the terminator is not a `Drop`, or if the dropped type is still generic
+    /// after instantiation.
+    pub fn drop_instance(&self, term: &Terminator, instance: &Instance) -> Option<Instance> {
+        let TerminatorKind::Drop { place, .. } = &term.kind else { return None };
+        let ty = place.ty(self.locals()).ok()?;
+        let ty = with(|cx| cx.instance_instantiate_ty(instance.def, ty)).ok()?;
+        if ty.visit(&mut GenericFinder).is_break() {
+            return None;
+        }
+        Some(Instance::resolve_drop_in_place(ty))
+    }
+
+    /// Check whether the given `Call` terminator can never return to its caller.
+    ///
+    /// A call diverges if the return type of the callee is `!` or any other uninhabited type,
+    /// in which case its `target` is unreachable. The terminator must belong to this body.
+    /// Returns `false` if the terminator is not a `Call`.
+    pub fn call_diverges(&self, term: &Terminator) -> bool {
+        let TerminatorKind::Call { func, .. } = &term.kind else { return false };
+        let Some(sig) = func.ty(self.locals()).ok().and_then(|ty| ty.fn_sig()) else {
+            return false;
+        };
+        sig.skip_binder().output().is_uninhabited()
+    }
+
+    /// Check whether this body does nothing but return unit.
+    ///
+    /// A body is trivial if it has a single block that returns right away. Storage markers and
+    /// `Nop` statements are ignored, since they have no effect.
+    pub fn is_trivial(&self) -> bool {
+        let [block] = &self.blocks[..] else { return false };
+        matches!(block.terminator.kind, TerminatorKind::Return)
+            && block.statements.iter().all(|stmt| {
+                matches!(
+                    stmt.kind,
+                    StatementKind::StorageLive(_)
+                        | StatementKind::StorageDead(_)
+                        | StatementKind::Nop
+                )
+            })
+            && self.ret_local().ty.kind().is_unit()
+    }
+
+    fn blocks_with_terminator(
+        &self,
+        predicate: impl Fn(&TerminatorKind) -> bool,
+    ) -> Vec<BasicBlockIdx> {
+        self.blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, bb)| predicate(&bb.terminator.kind).then_some(idx))
+            .collect()
+    }
+}
+
+/// Stops at the first generic parameter found in a type.
+struct GenericFinder;
+
+impl Visitor for GenericFinder {
+    type Break = ();
+
+    fn visit_ty(&mut self, ty: &Ty) -> ControlFlow<Self::Break> {
+        if let TyKind::Param(_) = ty.kind() { ControlFlow::Break(()) } else { ty.super_visit(self) }
+    }
+
+    fn visit_const(&mut self, c: &TyConst) -> ControlFlow<Self::Break> {
+        if let TyConstKind::Param(_) = c.kind() {
+            ControlFlow::Break(())
+        } else {
+            c.super_visit(self)
+        }
+    }
+}
+
+/// Replaces the `Self` type parameter in the types and constants of a body.
+struct SelfReplacer {
+    self_ty: Ty,
+}
+
+impl SelfReplacer {
+    fn replace_body(&self, body: &mut Body) {
+        for decl in &mut body.locals {
+            self.replace_ty(&mut decl.ty);
+        }
+        for block in &mut body.blocks {
+            for stmt in &mut block.statements {
+                self.replace_statement(&mut stmt.kind);
+            }
+            self.replace_terminator(&mut block.terminator.kind);
+        }
+        for info in &mut body.var_debug_info {
+            if let Some(fragment) = &mut info.composite {
+                self.replace_ty(&mut fragment.ty);
+                self.replace_projection(&mut fragment.projection);
+            }
+            match &mut info.value {
+                VarDebugInfoContents::Place(place) => self.replace_place(place),
+                VarDebugInfoContents::Const(constant) => {
+                    self.replace_mir_const(&mut constant.const_)
+                }
+            }
+        }
+        if let Some(layout) = &mut body.coroutine_layout {
+            for ty in &mut layout.field_tys {
+                self.replace_ty(ty);
+            }
+        }
+    }
+
+    fn replace_statement(&self, kind: &mut StatementKind) {
+        match kind {
+            StatementKind::Assign(place, rvalue) => {
+                self.replace_place(place);
+                self.replace_rvalue(rvalue);
+            }
+            StatementKind::FakeRead(_, place)
+            | StatementKind::SetDiscriminant { place, .. }
+            | StatementKind::Deinit(place)
+            | StatementKind::Retag(_, place)
+            | StatementKind::PlaceMention(place)
+            | StatementKind::AscribeUserType { place, .. } => self.replace_place(place),
+            StatementKind::Intrinsic(NonDivergingIntrinsic::Assume(operand)) => {
+                self.replace_operand(operand)
+            }
+            StatementKind::Intrinsic(NonDivergingIntrinsic::CopyNonOverlapping(copy)) => {
+                self.replace_operand(&mut copy.src);
+                self.replace_operand(&mut copy.dst);
+                self.replace_operand(&mut copy.count);
+            }
+            StatementKind::StorageLive(_)
+            | StatementKind::StorageDead(_)
+            | StatementKind::Coverage(_)
+            | StatementKind::ConstEvalCounter
+            | StatementKind::Nop => {}
+        }
+    }
+
+    fn replace_terminator(&self, kind: &mut TerminatorKind) {
+        match kind {
+            TerminatorKind::SwitchInt { discr, .. } => self.replace_operand(discr),
+            TerminatorKind::Drop { place, .. } => self.replace_place(place),
+            TerminatorKind::Call { func, args, destination, .. } => {
+                self.replace_operand(func);
+                for arg in args {
+                    self.replace_operand(arg);
+                }
+                self.replace_place(destination);
+            }
+            TerminatorKind::TailCall { func, args } => {
+                self.replace_operand(func);
+                for arg in args {
+                    self.replace_operand(arg);
+                }
+            }
+            TerminatorKind::Assert { cond, msg, .. } => {
+                self.replace_operand(cond);
+                match msg {
+                    AssertMessage::BoundsCheck { len: first, index: second }
+                    | AssertMessage::Overflow(_, first, second)
+                    | AssertMessage::MisalignedPointerDereference {
+                        required: first,
+                        found: second,
+                    } => {
+                        self.replace_operand(first);
+                        self.replace_operand(second);
+                    }
+                    AssertMessage::OverflowNeg(operand)
+                    | AssertMessage::DivisionByZero(operand)
+                    | AssertMessage::RemainderByZero(operand) => self.replace_operand(operand),
+                    AssertMessage::ResumedAfterReturn(_) | AssertMessage::ResumedAfterPanic(_) => {}
+                }
+            }
+            TerminatorKind::InlineAsm { operands, .. } => {
+                for operand in operands {
+                    if let Some(in_value) = &mut operand.in_value {
+                        self.replace_operand(in_value);
+                    }
+                    if let Some(out_place) = &mut operand.out_place {
+                        self.replace_place(out_place);
+                    }
+                }
+            }
+            TerminatorKind::Goto { .. }
+            | TerminatorKind::Resume
+            | TerminatorKind::Abort
+            | TerminatorKind::Return
+            | TerminatorKind::Unreachable => {}
+        }
+    }
+
+    fn replace_rvalue(&self, rvalue: &mut Rvalue) {
+        match rvalue {
+            Rvalue::AddressOf(_, place)
+            | Rvalue::CopyForDeref(place)
+            | Rvalue::Discriminant(place)
+            | Rvalue::Len(place)
+            | Rvalue::Ref(_, _, place) => self.replace_place(place),
+            Rvalue::Aggregate(kind, operands) => {
+                match kind {
+                    AggregateKind::Array(ty) | AggregateKind::RawPtr(ty, _) => self.replace_ty(ty),
+                    AggregateKind::Adt(_, _, args, _, _)
+                    | AggregateKind::Closure(_, args)
+                    | AggregateKind::Coroutine(_, args, _)
+                    | AggregateKind::CoroutineClosure(_, args) => self.replace_args(args),
+                    AggregateKind::Tuple => {}
+                }
+                for operand in operands {
+                    self.replace_operand(operand);
+                }
+            }
+            Rvalue::BinaryOp(_, lhs, rhs) | Rvalue::CheckedBinaryOp(_, lhs, rhs) => {
+                self.replace_operand(lhs);
+                self.replace_operand(rhs);
+            }
+            Rvalue::Cast(_, operand, ty) | Rvalue::ShallowInitBox(operand, ty) => {
+                self.replace_operand(operand);
+                self.replace_ty(ty);
+            }
+            Rvalue::Repeat(operand, count) => {
+                self.replace_operand(operand);
+                self.replace_ty_const(count);
+            }
+            Rvalue::NullaryOp(_, ty) => self.replace_ty(ty),
+            Rvalue::UnaryOp(_, operand) | Rvalue::Use(operand) => self.replace_operand(operand),
+            Rvalue::ThreadLocalRef(_) => {}
+        }
+    }
+
+    fn replace_operand(&self, operand: &mut Operand) {
+        match operand {
+            Operand::Copy(place) | Operand::Move(place) => self.replace_place(place),
+            Operand::Constant(constant) => self.replace_mir_const(&mut constant.const_),
+        }
+    }
+
+    fn replace_place(&self, place: &mut Place) {
+        self.replace_projection(&mut place.projection);
+    }
+
+    fn replace_projection(&self, projection: &mut [ProjectionElem]) {
+        for elem in projection {
+            match elem {
+                ProjectionElem::Field(_, ty)
+                | ProjectionElem::OpaqueCast(ty)
+                | ProjectionElem::Subtype(ty) => self.replace_ty(ty),
+                ProjectionElem::Deref
+                | ProjectionElem::Index(_)
+                | ProjectionElem::ConstantIndex { .. }
+                | ProjectionElem::Subslice { .. }
+                | ProjectionElem::Downcast(_) => {}
+            }
+        }
+    }
+
+    fn replace_args(&self, args: &mut GenericArgs) {
+        for arg in &mut args.0 {
+            match arg {
+                GenericArgKind::Type(ty) => self.replace_ty(ty),
+                GenericArgKind::Const(ct) => self.replace_ty_const(ct),
+                GenericArgKind::Lifetime(_) => {}
+            }
+        }
+    }
+
+    fn replace_ty(&self, ty: &mut Ty) {
+        *ty = with(|cx| cx.ty_replace_self(*ty, self.self_ty));
+    }
+
+    fn replace_ty_const(&self, ct: &mut TyConst) {
+        *ct = with(|cx| cx.ty_const_replace_self(ct, self.self_ty));
+    }
+
+    fn replace_mir_const(&self, cnst: &mut MirConst) {
+        *cnst = with(|cx| cx.mir_const_replace_self(cnst, self.self_ty));
+    }
 }
 
 type LocalDecls = Vec<LocalDecl>;
 
+/// The index of a local that is saved in the coroutine state across suspension points.
+pub type CoroutineSavedLocal = usize;
+
+/// The layout of the state of a coroutine.
+///
+/// Values that are yielded at suspension points are written to the return local, wrapped in
+/// the coroutine state, so there is no dedicated local for them.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct CoroutineLayout {
+    /// The type of every local that is saved across suspension points.
+    pub field_tys: Vec<Ty>,
+
+    /// The saved locals that are stored in each variant of the coroutine state.
+    ///
+    /// The first three variants are the unresumed, returned and poisoned states. Every other
+    /// variant represents one suspension point. A saved local may be stored in multiple variants.
+    pub variant_fields: Vec<Vec<CoroutineSavedLocal>>,
+
+    /// The span of the source of each variant, e.g., a `yield` or an `.await`.
+    pub variant_spans: Vec<Span>,
+
+    /// The local that holds the argument passed to the coroutine when it is resumed.
+    ///
+    /// This is `None` for coroutines that don't take a resume argument, such as `gen` blocks.
+    pub resume_local: Option<Local>,
+}
+
+impl CoroutineLayout {
+    /// The number of variants of the coroutine state that are not suspension points.
+    pub const RESERVED_VARIANTS: usize = 3;
+
+    /// The variants of the coroutine state that represent suspension points.
+    pub fn suspension_variants(&self) -> impl Iterator<Item = VariantIdx> {
+        (Self::RESERVED_VARIANTS..self.variant_fields.len()).map(VariantIdx::to_val)
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct LocalDecl {
     pub ty: Ty,
@@ -114,6 +709,15 @@ pub struct LocalDecl {
     pub mutability: Mutability,
 }
 
+/// Identifies a statement or a terminator in a body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct MirLocation {
+    pub block: BasicBlockIdx,
+    /// The index of the statement in the block. The terminator index is the number of
+    /// statements in the block.
+    pub statement_index: usize,
+}
+
 #[derive(Clone, PartialEq, Eq, Debug, Serialize)]
 pub struct BasicBlock {
     pub statements: Vec<Statement>,
@@ -130,6 +734,12 @@ impl Terminator {
     pub fn successors(&self) -> Successors {
         self.kind.successors()
     }
+
+    /// Whether unwinding may continue from this terminator, either to a cleanup block or to the
+    /// caller.
+    pub fn can_unwind(&self) -> bool {
+        self.kind.can_unwind()
+    }
 }
 
 pub type Successors = Vec<BasicBlockIdx>;
@@ -159,6 +769,15 @@ pub enum TerminatorKind {
         target: Option<BasicBlockIdx>,
         unwind: UnwindAction,
     },
+    /// A call that replaces the current stack frame, i.e., an explicit tail call done via
+    /// `become`.
+    ///
+    /// The value returned by the callee is returned directly to the caller of the current
+    /// function, thus there is no destination nor target.
+    TailCall {
+        func: Operand,
+        args: Vec<Operand>,
+    },
     Assert {
         cond: Operand,
         expected: bool,
@@ -201,6 +820,7 @@ pub fn successors(&self) -> Successors {
             | Abort
             | Unreachable
             | Call { target: None, unwind: _, .. }
+            | TailCall { .. }
             | InlineAsm { destination: None, unwind: _, .. } => {
                 vec![]
             }
@@ -208,6 +828,14 @@ pub fn successors(&self) -> Successors {
         }
     }
 
+    /// Whether unwinding may continue from this terminator, either to a cleanup block or to the
+    /// caller.
+    ///
+    /// Terminators whose unwind action is `Unreachable` or `Terminate` never unwind.
+    pub fn can_unwind(&self) -> bool {
+        matches!(self.unwind(), Some(UnwindAction::Cleanup(_) | UnwindAction::Continue))
+    }
+
     pub fn unwind(&self) -> Option<&UnwindAction> {
         match *self {
             TerminatorKind::Goto { .. }
@@ -215,6 +843,7 @@ pub fn unwind(&self) -> Option<&UnwindAction> {
             | TerminatorKind::Unreachable
             | TerminatorKind::Resume
             | TerminatorKind::Abort
+            | TerminatorKind::TailCall { .. }
             | TerminatorKind::SwitchInt { .. } => None,
             TerminatorKind::Call { ref unwind, .. }
             | TerminatorKind::Assert { ref unwind, .. }
@@ -228,11 +857,40 @@ pub fn unwind(&self) -> Option<&UnwindAction> {
 pub struct InlineAsmOperand {
     pub in_value: Option<Operand>,
     pub out_place: Option<Place>,
+    /// The direction of this operand, or `None` for operands that are not passed in a register,
+    /// such as `const` and `sym` operands.
+    pub direction: Option<InlineAsmDirection>,
+    /// The register used by this operand, or `None` for operands that are not passed in a
+    /// register.
+    pub reg: Option<InlineAsmReg>,
     // This field has a raw debug representation of MIR's InlineAsmOperand.
     // For now we care about place/operand + the rest in a debug format.
     pub raw_rpr: String,
 }
 
+/// The direction in which data flows through an inline assembly register operand.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize)]
+pub enum InlineAsmDirection {
+    /// An `in` operand.
+    In,
+    /// An `out` operand, or a `lateout` operand if `late` is set.
+    ///
+    /// Late outputs may share a register with an input, since they are only written after all
+    /// inputs have been read.
+    Out { late: bool },
+    /// An `inout` operand, or an `inlateout` operand if `late` is set.
+    InOut { late: bool },
+}
+
+/// The register of an inline assembly operand.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize)]
+pub enum InlineAsmReg {
+    /// An explicit register, e.g., `"eax"`.
+    Reg(Symbol),
+    /// Any register of the given class, e.g., `reg`.
+    RegClass(Symbol),
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize)]
 pub enum UnwindAction {
     Continue,
@@ -716,6 +1374,18 @@ pub struct SourceInfo {
     pub scope: SourceScope,
 }
 
+/// A lexical scope of a body.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct SourceScopeData {
+    pub span: Span,
+    /// The scope that contains this one, if any.
+    pub parent_scope: Option<SourceScope>,
+    /// The instance whose body was inlined in this scope, and the span of the inlined call.
+    ///
+    /// This is only set for the outermost scope of an inlined body.
+    pub inlined: Option<(Instance, Span)>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct VarDebugInfoFragment {
     pub ty: Ty,
@@ -820,6 +1490,19 @@ pub struct UserTypeProjection {
 
 pub const RETURN_LOCAL: Local = 0;
 
+/// Classifies the locals of a body, see [Body::local_kind].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum LocalKind {
+    /// The local that holds the return value.
+    ReturnPointer,
+    /// A function argument.
+    Arg,
+    /// A user-declared variable, i.e., a local with debug information.
+    Var,
+    /// A temporary introduced by the compiler.
+    Temp,
+}
+
 /// The source-order index of a field in a variant.
 ///
 /// For example, in the following types,
@@ -834,7 +1517,18 @@ pub struct UserTypeProjection {
 /// `b`'s `FieldIdx` is `1`,
 /// `c`'s `FieldIdx` is `0`, and
 /// `g`'s `FieldIdx` is `2`.
-pub type FieldIdx = usize;
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct FieldIdx(usize);
+
+impl IndexedVal for FieldIdx {
+    fn to_val(index: usize) -> Self {
+        FieldIdx(index)
+    }
+
+    fn to_index(&self) -> usize {
+        self.0
+    }
+}
 
 type UserTypeAnnotationIndex = usize;
 
@@ -870,6 +1564,17 @@ pub fn len(&self) -> usize {
         self.branches.len() + 1
     }
 
+    /// The target that is taken when the switch operand evaluates to the given value.
+    ///
+    /// This returns the target of the first branch guarded by `value`, or the `otherwise` target
+    /// if no branch matches.
+    pub fn target_for_value(&self, value: u128) -> BasicBlockIdx {
+        self.branches
+            .iter()
+            .find_map(|(val, target)| (*val == value).then_some(*target))
+            .unwrap_or(self.otherwise)
+    }
+
     /// Create a new SwitchTargets from the given branches and `otherwise` target.
     pub fn new(branches: Vec<(u128, BasicBlockIdx)>, otherwise: BasicBlockIdx) -> SwitchTargets {
         SwitchTargets { branches, otherwise }
@@ -1056,7 +1761,7 @@ fn subslice_ty(ty: Ty, from: u64, to: u64, from_end: bool) -> Result<Ty, Error>
                 let len = size - from - to;
                 Ty::try_new_array(inner, len)
             }
-            _ => Err(Error(format!("Cannot subslice non-array type: `{ty_kind:?}`"))),
+            _ => Err(error!("Cannot subslice non-array type: `{ty_kind:?}`")),
         }
     }
 
@@ -0,0 +1,95 @@
+//! Structural comparison of MIR bodies.
+//!
+//! This can be used to inspect the effect of a transformation on a body, e.g., for snapshot
+//! testing. Blocks are matched by their index, and statements are compared by their kind only,
+//! so changes to spans alone are not reported.
+
+use crate::mir::{BasicBlock, BasicBlockIdx, Body, Statement};
+
+/// The structural differences between two bodies, as computed by [body_diff].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BodyDiff {
+    /// Blocks that only exist in the second body.
+    pub added_blocks: Vec<BasicBlockIdx>,
+    /// Blocks that only exist in the first body.
+    pub removed_blocks: Vec<BasicBlockIdx>,
+    /// Blocks that exist in both bodies, but whose contents differ.
+    pub changed_blocks: Vec<BlockDiff>,
+}
+
+impl BodyDiff {
+    /// Whether both bodies are structurally the same.
+    pub fn is_empty(&self) -> bool {
+        self.added_blocks.is_empty()
+            && self.removed_blocks.is_empty()
+            && self.changed_blocks.is_empty()
+    }
+}
+
+/// The differences between two versions of the same basic block.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlockDiff {
+    pub block: BasicBlockIdx,
+    /// The index of the statements that only exist in the second version of the block.
+    pub added_statements: Vec<usize>,
+    /// The index of the statements that only exist in the first version of the block.
+    pub removed_statements: Vec<usize>,
+    /// Whether the terminator kind differs.
+    pub terminator_changed: bool,
+}
+
+/// Compute the structural differences from body `a` to body `b`.
+pub fn body_diff(a: &Body, b: &Body) -> BodyDiff {
+    let common = a.blocks.len().min(b.blocks.len());
+    BodyDiff {
+        added_blocks: (common..b.blocks.len()).collect(),
+        removed_blocks: (common..a.blocks.len()).collect(),
+        changed_blocks: (0..common)
+            .filter_map(|idx| block_diff(idx, &a.blocks[idx], &b.blocks[idx]))
+            .collect(),
+    }
+}
+
+fn block_diff(block: BasicBlockIdx, a: &BasicBlock, b: &BasicBlock) -> Option<BlockDiff> {
+    let (removed_statements, added_statements) = statements_diff(&a.statements, &b.statements);
+    let terminator_changed = a.terminator.kind != b.terminator.kind;
+    if added_statements.is_empty() && removed_statements.is_empty() && !terminator_changed {
+        None
+    } else {
+        Some(BlockDiff { block, added_statements, removed_statements, terminator_changed })
+    }
+}
+
+/// Compute which statements were removed from `a` and which were added to `b`, using the
+/// longest common subsequence of both statement lists.
+fn statements_diff(a: &[Statement], b: &[Statement]) -> (Vec<usize>, Vec<usize>) {
+    // `lcs[i][j]` is the length of the longest common subsequence of `a[i..]` and `b[j..]`.
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i].kind == b[j].kind {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut removed, mut added) = (vec![], vec![]);
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i].kind == b[j].kind {
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            removed.push(i);
+            i += 1;
+        } else {
+            added.push(j);
+            j += 1;
+        }
+    }
+    removed.extend(i..a.len());
+    added.extend(j..b.len());
+    (removed, added)
+}
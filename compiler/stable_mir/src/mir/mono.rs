@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::fmt::{Debug, Formatter};
 use std::io;
 
@@ -6,6 +7,7 @@
 use crate::abi::FnAbi;
 use crate::crate_def::CrateDef;
 use crate::mir::Body;
+use crate::mir::alloc::{AllocId, GlobalAlloc};
 use crate::ty::{Allocation, ClosureDef, ClosureKind, FnDef, GenericArgs, IndexedVal, Ty};
 use crate::{CrateItem, DefId, Error, ItemKind, Opaque, Symbol, with};
 
@@ -16,6 +18,16 @@ pub enum MonoItem {
     GlobalAsm(Opaque),
 }
 
+/// A unit of code generation, i.e., a group of items that are compiled together into a single
+/// object file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CodegenUnit {
+    /// The name of this unit, which is unique among all the crates.
+    pub name: Symbol,
+    /// The items that are generated as part of this unit.
+    pub items: Vec<MonoItem>,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct Instance {
     /// The type of instance.
@@ -49,6 +61,9 @@ pub fn args(&self) -> GenericArgs {
     /// The body will be eagerly monomorphized and all constants will already be evaluated.
     ///
     /// This method will return the intrinsic fallback body if one was defined.
+    ///
+    /// For shims, e.g. reify, function pointer and closure once shims, this will return the body
+    /// generated by the compiler.
     pub fn body(&self) -> Option<Body> {
         with(|context| context.instance_body(self.def))
     }
@@ -75,11 +90,30 @@ pub fn ty(&self) -> Ty {
         with(|context| context.instance_ty(self.def))
     }
 
+    /// Retrieve the item that this instance was instantiated from.
+    ///
+    /// This returns `None` for shims and other instances that are not backed by an item's body,
+    /// such as intrinsics and virtual calls. Unlike the `TryFrom` conversion to [CrateItem], this
+    /// doesn't require the body of the item to be available, so it also works for items from
+    /// other crates.
+    pub fn crate_item(&self) -> Option<CrateItem> {
+        (self.kind == InstanceKind::Item)
+            .then(|| CrateItem(with(|context| context.instance_def_id(self.def))))
+    }
+
     /// Retrieve information about this instance binary interface.
     pub fn fn_abi(&self) -> Result<FnAbi, Error> {
         with(|cx| cx.instance_abi(self.def))
     }
 
+    /// Check whether calls to this instance take the caller location as an implicit argument.
+    ///
+    /// This is the case for `#[track_caller]` functions, and it affects the instance ABI and how
+    /// calls to it are lowered.
+    pub fn requires_caller_location(&self) -> bool {
+        with(|cx| cx.instance_requires_caller_location(self.def))
+    }
+
     /// Retrieve the instance's mangled name used for calling the given instance.
     ///
     /// This will also look up the correct name of instances from upstream crates.
@@ -168,6 +202,28 @@ pub fn is_empty_shim(&self) -> bool {
             })
     }
 
+    /// Check whether this instance is the drop glue of a type.
+    ///
+    /// Drop glue is reported as an [InstanceKind::Shim], so use this method to distinguish it
+    /// from other compiler generated shims.
+    pub fn is_drop_glue(&self) -> bool {
+        self.kind == InstanceKind::Shim && with(|cx| cx.is_drop_glue(self.def))
+    }
+
+    /// Get the body of the drop glue shim represented by this instance.
+    ///
+    /// This will return `None` if this instance is not a drop glue, or if the type being dropped
+    /// has no drop glue, i.e., if this is an empty shim.
+    ///
+    /// Drop glue instances can be retrieved via [Instance::resolve_drop_in_place].
+    pub fn drop_shim_body(&self) -> Option<Body> {
+        if self.is_drop_glue() && !with(|cx| cx.is_empty_drop_shim(self.def)) {
+            self.body()
+        } else {
+            None
+        }
+    }
+
     /// Try to constant evaluate the instance into a constant with the given type.
     ///
     /// This can be used to retrieve a constant that represents an intrinsic return such as
@@ -291,10 +347,43 @@ pub fn ty(&self) -> Ty {
         with(|cx| cx.def_ty(self.0))
     }
 
+    /// Retrieve the body of this static's initializer.
+    ///
+    /// Unlike [StaticDef::eval_initializer], which only returns the resulting value, this body
+    /// can be used to step through the computation of the initial value. This returns `None` for
+    /// foreign statics, and for statics whose body is not available.
+    pub fn initializer_body(&self) -> Option<Body> {
+        with(|cx| cx.initializer_body(self.0))
+    }
+
     /// Evaluate a static's initializer, returning the allocation of the initializer's memory.
     pub fn eval_initializer(&self) -> Result<Allocation, Error> {
         with(|cx| cx.eval_static_initializer(*self))
     }
+
+    /// Collect every allocation that is transitively referenced by this static's initializer.
+    ///
+    /// The traversal follows the pointers stored in memory allocations. It does not follow the
+    /// pointers inside the initializer of other statics, since those are only referenced through
+    /// [GlobalAlloc::Static].
+    #[allow(rustc::default_hash_types)]
+    pub fn reachable_allocs(&self) -> Result<Vec<(AllocId, GlobalAlloc)>, Error> {
+        let mut visited = HashSet::new();
+        let mut pending: Vec<AllocId> =
+            self.eval_initializer()?.provenance.ptrs.iter().map(|(_, prov)| prov.0).collect();
+        let mut allocs = vec![];
+        while let Some(alloc_id) = pending.pop() {
+            if !visited.insert(alloc_id) {
+                continue;
+            }
+            let global_alloc = GlobalAlloc::from(alloc_id);
+            if let GlobalAlloc::Memory(alloc) = &global_alloc {
+                pending.extend(alloc.provenance.ptrs.iter().map(|(_, prov)| prov.0));
+            }
+            allocs.push((alloc_id, global_alloc));
+        }
+        Ok(allocs)
+    }
 }
 
 impl IndexedVal for InstanceDef {
@@ -6,7 +6,10 @@
 use fmt::{Display, Formatter};
 
 use super::{AggregateKind, AssertMessage, BinOp, BorrowKind, FakeBorrowKind, TerminatorKind};
-use crate::mir::{Operand, Place, Rvalue, StatementKind, UnwindAction, VarDebugInfoContents};
+use crate::mir::visit::{Location, PlaceContext, statement_location, terminator_location};
+use crate::mir::{
+    Local, MirVisitor, Operand, Place, Rvalue, StatementKind, UnwindAction, VarDebugInfoContents,
+};
 use crate::ty::{AdtKind, IndexedVal, MirConst, Ty, TyConst};
 use crate::{Body, CrateDef, Mutability, with};
 
@@ -22,7 +25,36 @@ fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
     }
 }
 
+/// Options that control how [render_with] prints a function body.
+///
+/// The default options produce the same output as [Body::dump].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PrettyOptions {
+    /// Annotate each statement and terminator with the location of its source code.
+    pub spans: bool,
+    /// Annotate each statement and terminator with the types of the locals that it uses.
+    pub local_types: bool,
+    /// Prefix each statement and terminator with its index inside its basic block.
+    pub numbered: bool,
+}
+
+/// Render a function body using the given name for its signature, and the given options.
+pub fn render_with(body: &Body, name: &str, opts: PrettyOptions) -> String {
+    let mut buf = Vec::new();
+    function_body_with(&mut buf, body, name, opts).expect("writing to a buffer cannot fail");
+    String::from_utf8(buf).expect("MIR output should be valid UTF-8")
+}
+
 pub(crate) fn function_body<W: Write>(writer: &mut W, body: &Body, name: &str) -> io::Result<()> {
+    function_body_with(writer, body, name, PrettyOptions::default())
+}
+
+fn function_body_with<W: Write>(
+    writer: &mut W,
+    body: &Body,
+    name: &str,
+    opts: PrettyOptions,
+) -> io::Result<()> {
     write!(writer, "fn {name}(")?;
     let mut sep = "";
     for (index, local) in body.arg_locals().iter().enumerate() {
@@ -43,37 +75,136 @@ pub(crate) fn function_body<W: Write>(writer: &mut W, body: &Body, name: &str) -
     })?;
 
     body.var_debug_info.iter().try_for_each(|info| {
-        let content = match &info.value {
-            VarDebugInfoContents::Place(place) => {
-                format!("{place:?}")
-            }
-            VarDebugInfoContents::Const(constant) => pretty_mir_const(&constant.const_),
-        };
-        writeln!(writer, "    debug {} => {};", info.name, content)
+        writeln!(writer, "    debug {} => {};", info.name, pretty_debug_info_value(&info.value))
     })?;
 
-    body.blocks
-        .iter()
-        .enumerate()
-        .map(|(index, block)| -> io::Result<()> {
-            writeln!(writer, "    bb{index}: {{")?;
-            let _ = block
-                .statements
-                .iter()
-                .map(|statement| -> io::Result<()> {
-                    pretty_statement(writer, &statement.kind)?;
-                    Ok(())
-                })
-                .collect::<Vec<_>>();
-            pretty_terminator(writer, &block.terminator.kind)?;
-            writeln!(writer, "    }}").unwrap();
-            Ok(())
-        })
-        .collect::<Result<Vec<_>, _>>()?;
+    for (index, block) in body.blocks.iter().enumerate() {
+        writeln!(writer, "    bb{index}: {{")?;
+        for (statement_index, statement) in block.statements.iter().enumerate() {
+            let location = statement_location(body, &index, statement_index);
+            let mut line = Vec::new();
+            pretty_statement(&mut line, &statement.kind)?;
+            // Only the local types annotation needs the locals used by the statement.
+            let mut collector = LocalCollector::default();
+            if opts.local_types {
+                collector.visit_statement(statement, location);
+            }
+            collector.write_annotated(writer, body, opts, statement_index, location, &line)?;
+        }
+        let location = terminator_location(body, &index);
+        let mut line = Vec::new();
+        pretty_terminator(&mut line, &block.terminator.kind)?;
+        let mut collector = LocalCollector::default();
+        if opts.local_types {
+            collector.visit_terminator(&block.terminator, location);
+        }
+        let terminator_index = block.statements.len();
+        collector.write_annotated(writer, body, opts, terminator_index, location, &line)?;
+        writeln!(writer, "    }}")?;
+    }
     writeln!(writer, "}}")?;
     Ok(())
 }
 
+/// Collect the locals used by a statement or terminator, in the order they are found.
+///
+/// The collector is only filled in when [PrettyOptions::local_types] is set.
+#[derive(Default)]
+struct LocalCollector {
+    locals: Vec<Local>,
+}
+
+impl LocalCollector {
+    /// Write the line of the visited statement or terminator, annotated according to `opts`.
+    fn write_annotated<W: Write>(
+        &self,
+        writer: &mut W,
+        body: &Body,
+        opts: PrettyOptions,
+        index: usize,
+        location: Location,
+        line: &[u8],
+    ) -> io::Result<()> {
+        if opts == PrettyOptions::default() {
+            return writer.write_all(line);
+        }
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim_end();
+        let code = line.trim_start();
+        write!(writer, "{}", &line[..line.len() - code.len()])?;
+        if opts.numbered {
+            write!(writer, "{index}: ")?;
+        }
+        write!(writer, "{code}")?;
+        let mut notes = Vec::new();
+        if opts.local_types {
+            let decls = body.locals();
+            notes.extend(self.locals.iter().map(|local| format!("_{local}: {}", decls[*local].ty)));
+        }
+        if opts.spans {
+            notes.push(location.span().diagnostic());
+        }
+        if !notes.is_empty() {
+            write!(writer, " // {}", notes.join(", "))?;
+        }
+        writeln!(writer)
+    }
+}
+
+impl MirVisitor for LocalCollector {
+    fn visit_local(&mut self, local: &Local, _ptx: PlaceContext, _location: Location) {
+        if !self.locals.contains(local) {
+            self.locals.push(*local);
+        }
+    }
+}
+
+/// Emit the control-flow graph of a function body in the Graphviz DOT format.
+///
+/// Each node is labeled with the statements and the terminator of the basic block. Unwind edges
+/// are dashed.
+pub(crate) fn function_body_dot<W: Write>(
+    writer: &mut W,
+    body: &Body,
+    name: &str,
+) -> io::Result<()> {
+    writeln!(writer, "digraph \"{}\" {{", dot_escape(name))?;
+    writeln!(writer, "    node [shape=box, fontname=\"monospace\"];")?;
+    for (index, block) in body.blocks.iter().enumerate() {
+        let mut contents = Vec::new();
+        writeln!(contents, "bb{index}:")?;
+        for statement in &block.statements {
+            pretty_statement(&mut contents, &statement.kind)?;
+        }
+        pretty_terminator(&mut contents, &block.terminator.kind)?;
+        let label: String = String::from_utf8_lossy(&contents)
+            .lines()
+            .map(|line| format!("{}\\l", dot_escape(line.trim())))
+            .collect();
+        writeln!(writer, "    bb{index} [label=\"{label}\"];")?;
+    }
+    for (index, block) in body.blocks.iter().enumerate() {
+        let terminator = &block.terminator.kind;
+        let successors = terminator.successors();
+        let labels = pretty_successor_labels(terminator);
+        // The cleanup block is always the last successor.
+        let has_cleanup = matches!(terminator.unwind(), Some(UnwindAction::Cleanup(_)));
+        for (i, target) in successors.iter().enumerate() {
+            let label = labels.get(i).map_or("", |label| label.as_str());
+            write!(writer, "    bb{index} -> bb{target} [label=\"{}\"", dot_escape(label))?;
+            if has_cleanup && i + 1 == successors.len() {
+                write!(writer, ", style=dashed")?;
+            }
+            writeln!(writer, "];")?;
+        }
+    }
+    writeln!(writer, "}}")
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
 fn pretty_statement<W: Write>(writer: &mut W, statement: &StatementKind) -> io::Result<()> {
     const INDENT: &str = "        ";
     match statement {
@@ -176,6 +307,13 @@ fn pretty_terminator_head<W: Write>(writer: &mut W, terminator: &TerminatorKind)
             args_iter.try_for_each(|arg| write!(writer, ", {}", pretty_operand(arg)))?;
             write!(writer, ")")
         }
+        TailCall { func, args } => {
+            write!(writer, "{INDENT}tailcall {}(", pretty_operand(func))?;
+            let mut args_iter = args.iter();
+            args_iter.next().map_or(Ok(()), |arg| write!(writer, "{}", pretty_operand(arg)))?;
+            args_iter.try_for_each(|arg| write!(writer, ", {}", pretty_operand(arg)))?;
+            write!(writer, ")")
+        }
         Assert { cond, expected, msg, target: _, unwind: _ } => {
             write!(writer, "{INDENT}assert(")?;
             if !expected {
@@ -194,7 +332,12 @@ fn pretty_successor_labels(terminator: &TerminatorKind) -> Vec<String> {
     match terminator {
         Call { target: None, unwind: UnwindAction::Cleanup(_), .. }
         | InlineAsm { destination: None, .. } => vec!["unwind".into()],
-        Resume | Abort | Return | Unreachable | Call { target: None, unwind: _, .. } => vec![],
+        Resume
+        | Abort
+        | Return
+        | Unreachable
+        | TailCall { .. }
+        | Call { target: None, unwind: _, .. } => vec![],
         Goto { .. } => vec!["".to_string()],
         SwitchInt { targets, .. } => targets
             .branches()
@@ -314,6 +457,14 @@ fn pretty_operand(operand: &Operand) -> String {
     }
 }
 
+/// Render where the value of a user variable is found, as printed by [Body::dump].
+pub(crate) fn pretty_debug_info_value(value: &VarDebugInfoContents) -> String {
+    match value {
+        VarDebugInfoContents::Place(place) => format!("{place:?}"),
+        VarDebugInfoContents::Const(constant) => pretty_mir_const(&constant.const_),
+    }
+}
+
 fn pretty_mir_const(literal: &MirConst) -> String {
     with(|cx| cx.mir_const_pretty(literal))
 }
@@ -35,8 +35,9 @@
 //! The only place that `_` is acceptable is to match a field (or
 //! variant argument) that does not require visiting.
 
+use crate::mir::alloc::AllocId;
 use crate::mir::*;
-use crate::ty::{GenericArgs, MirConst, Region, Ty, TyConst};
+use crate::ty::{ConstantKind, GenericArgs, MirConst, Region, Ty, TyConst};
 use crate::{Error, Opaque, Span};
 
 pub trait MirVisitor {
@@ -116,6 +117,23 @@ fn visit_mir_const(&mut self, constant: &MirConst, location: Location) {
         self.super_mir_const(constant, location)
     }
 
+    /// Visit the type of a MIR constant.
+    ///
+    /// By default, this visits the type like any other type in the body. Visitors that are only
+    /// interested in the constants can override this method to skip their types.
+    fn visit_mir_const_ty(&mut self, ty: &Ty, location: Location) {
+        self.visit_ty(ty, location)
+    }
+
+    /// Visit an allocation that a MIR constant points to.
+    ///
+    /// This is only called for the pointers stored in evaluated constants. The other parts of a
+    /// constant, such as the arguments of an unevaluated constant, are not visited.
+    fn visit_alloc_id(&mut self, alloc_id: &AllocId, location: Location) {
+        let _ = location;
+        self.super_alloc_id(alloc_id)
+    }
+
     fn visit_ty_const(&mut self, constant: &TyConst, location: Location) {
         let _ = location;
         self.super_ty_const(constant)
@@ -126,6 +144,11 @@ fn visit_region(&mut self, region: &Region, location: Location) {
         self.super_region(region)
     }
 
+    fn visit_borrow_kind(&mut self, kind: &BorrowKind, location: Location) {
+        let _ = location;
+        self.super_borrow_kind(kind)
+    }
+
     fn visit_args(&mut self, args: &GenericArgs, location: Location) {
         let _ = location;
         self.super_args(args)
@@ -140,7 +163,16 @@ fn visit_var_debug_info(&mut self, var_debug_info: &VarDebugInfo) {
     }
 
     fn super_body(&mut self, body: &Body) {
-        let Body { blocks, locals: _, arg_count, var_debug_info, spread_arg: _, span } = body;
+        let Body {
+            blocks,
+            locals: _,
+            arg_count,
+            var_debug_info,
+            spread_arg: _,
+            span,
+            coroutine_layout: _,
+            source_scopes: _,
+        } = body;
 
         for bb in blocks {
             self.visit_basic_block(bb);
@@ -250,9 +282,16 @@ fn super_terminator(&mut self, term: &Terminator, location: Location) {
                 }
                 self.visit_place(destination, PlaceContext::MUTATING, location);
             }
+            TerminatorKind::TailCall { func, args } => {
+                self.visit_operand(func, location);
+                for arg in args {
+                    self.visit_operand(arg, location);
+                }
+            }
             TerminatorKind::InlineAsm { operands, .. } => {
                 for op in operands {
-                    let InlineAsmOperand { in_value, out_place, raw_rpr: _ } = op;
+                    let InlineAsmOperand { in_value, out_place, direction: _, reg: _, raw_rpr: _ } =
+                        op;
                     if let Some(input) = in_value {
                         self.visit_operand(input, location);
                     }
@@ -308,7 +347,7 @@ fn super_projection_elem(
     fn super_rvalue(&mut self, rvalue: &Rvalue, location: Location) {
         match rvalue {
             Rvalue::AddressOf(mutability, place) => {
-                let pcx = PlaceContext { is_mut: *mutability == Mutability::Mut };
+                let pcx = PlaceContext { is_mut: *mutability == Mutability::Mut, is_use: true };
                 self.visit_place(place, pcx, location);
             }
             Rvalue::Aggregate(_, operands) => {
@@ -329,7 +368,9 @@ fn super_rvalue(&mut self, rvalue: &Rvalue, location: Location) {
             }
             Rvalue::Ref(region, kind, place) => {
                 self.visit_region(region, location);
-                let pcx = PlaceContext { is_mut: matches!(kind, BorrowKind::Mut { .. }) };
+                self.visit_borrow_kind(kind, location);
+                let pcx =
+                    PlaceContext { is_mut: matches!(kind, BorrowKind::Mut { .. }), is_use: true };
                 self.visit_place(place, pcx, location);
             }
             Rvalue::Repeat(op, constant) => {
@@ -377,8 +418,17 @@ fn super_const_operand(&mut self, constant: &ConstOperand, location: Location) {
     }
 
     fn super_mir_const(&mut self, constant: &MirConst, location: Location) {
-        let MirConst { kind: _, ty, id: _ } = constant;
-        self.visit_ty(ty, location);
+        let MirConst { kind, ty, id: _ } = constant;
+        self.visit_mir_const_ty(ty, location);
+        if let ConstantKind::Allocated(alloc) = kind {
+            for (_, prov) in &alloc.provenance.ptrs {
+                self.visit_alloc_id(&prov.0, location);
+            }
+        }
+    }
+
+    fn super_alloc_id(&mut self, alloc_id: &AllocId) {
+        let _ = alloc_id;
     }
 
     fn super_ty_const(&mut self, constant: &TyConst) {
@@ -389,6 +439,10 @@ fn super_region(&mut self, region: &Region) {
         let _ = region;
     }
 
+    fn super_borrow_kind(&mut self, kind: &BorrowKind) {
+        let _ = kind;
+    }
+
     fn super_args(&mut self, args: &GenericArgs) {
         let _ = args;
     }
@@ -453,6 +507,16 @@ impl Location {
     pub fn span(&self) -> Span {
         self.0
     }
+
+    /// The line and column where the source code of this location starts, both starting at 1.
+    ///
+    /// These are only computed when this method is called. Returns `None` if the span of this
+    /// location doesn't point to any source file, e.g., for compiler generated code without a
+    /// meaningful span.
+    pub fn line_col(&self) -> Option<(usize, usize)> {
+        let lines = self.0.get_lines();
+        (lines.start_line > 0).then_some((lines.start_line, lines.start_col))
+    }
 }
 
 /// Location of the statement at the given index for a given basic block. Assumes that `stmt_idx`
@@ -482,6 +546,23 @@ impl PlaceRef<'_> {
     pub fn ty(&self, locals: &[LocalDecl]) -> Result<Ty, Error> {
         self.projection.iter().fold(Ok(locals[self.local].ty), |place_ty, elem| elem.ty(place_ty?))
     }
+
+    /// Whether this place accesses a field of a union, which requires an `unsafe` block.
+    ///
+    /// This checks every field projection of this place, so it also returns `true` for places
+    /// that access a field nested inside a union field. If the type of a projection cannot be
+    /// computed, the projections after it are not checked.
+    pub fn is_union_access(&self, locals: &[LocalDecl]) -> bool {
+        let mut ty = locals[self.local].ty;
+        for elem in self.projection {
+            if matches!(elem, ProjectionElem::Field(..)) && ty.kind().is_union() {
+                return true;
+            }
+            let Ok(next) = elem.ty(ty) else { return false };
+            ty = next;
+        }
+        false
+    }
 }
 
 /// Information about a place's usage.
@@ -490,14 +571,58 @@ pub struct PlaceContext {
     /// Whether the access is mutable or not. Keep this private so we can increment the type in a
     /// backward compatible manner.
     is_mut: bool,
+    /// Whether the place is actually used, as opposed to being mentioned by statements such as
+    /// `StorageLive` or by debug information.
+    is_use: bool,
 }
 
 impl PlaceContext {
-    const MUTATING: Self = PlaceContext { is_mut: true };
-    const NON_MUTATING: Self = PlaceContext { is_mut: false };
-    const NON_USE: Self = PlaceContext { is_mut: false };
+    const MUTATING: Self = PlaceContext { is_mut: true, is_use: true };
+    const NON_MUTATING: Self = PlaceContext { is_mut: false, is_use: true };
+    const NON_USE: Self = PlaceContext { is_mut: false, is_use: false };
 
     pub fn is_mutating(&self) -> bool {
         self.is_mut
     }
+
+    /// Whether the place is used. Storage markers and debug information do not count as uses.
+    pub fn is_use(&self) -> bool {
+        self.is_use
+    }
+}
+
+/// The accesses to a local in a body, grouped by their [PlaceContext].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LocalUsage {
+    /// Locations where the local may be modified.
+    pub mutating: Vec<Location>,
+    /// Locations where the local is read, but not modified.
+    pub non_mutating: Vec<Location>,
+    /// Locations where the local is mentioned without being used, e.g., storage markers.
+    pub non_use: Vec<Location>,
+}
+
+impl LocalUsage {
+    /// Whether the local is ever modified, including its initialization.
+    pub fn is_mutated(&self) -> bool {
+        !self.mutating.is_empty()
+    }
+}
+
+/// Visitor that collects the usages of every local in a body.
+pub(crate) struct LocalUsageCollector {
+    pub(crate) usages: Vec<LocalUsage>,
+}
+
+impl MirVisitor for LocalUsageCollector {
+    fn visit_local(&mut self, local: &Local, ptx: PlaceContext, location: Location) {
+        let usage = &mut self.usages[*local];
+        if ptx.is_mutating() {
+            usage.mutating.push(location);
+        } else if ptx.is_use() {
+            usage.non_mutating.push(location);
+        } else {
+            usage.non_use.push(location);
+        }
+    }
 }
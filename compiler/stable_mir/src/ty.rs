@@ -5,12 +5,16 @@
 
 use super::mir::{Body, Mutability, Safety};
 use super::{DefId, Error, Symbol, with};
-use crate::abi::{FnAbi, Layout};
+use crate::abi::{FnAbi, Layout, Scalar, ValueAbi, WrappingRange};
 use crate::crate_def::{CrateDef, CrateDefType};
 use crate::mir::alloc::{AllocId, read_target_int, read_target_uint};
 use crate::mir::mono::StaticDef;
 use crate::target::MachineInfo;
-use crate::{Filename, Opaque};
+use crate::{CrateItem, Filename, ItemKind, Opaque};
+
+mod graph;
+
+pub use graph::{TypeGraph, type_graph};
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Serialize)]
 pub struct Ty(usize);
@@ -83,6 +87,16 @@ pub fn bool_ty() -> Ty {
         Ty::from_rigid_kind(RigidTy::Bool)
     }
 
+    /// Create a type representing the never type `!`.
+    pub fn never_ty() -> Ty {
+        Ty::from_rigid_kind(RigidTy::Never)
+    }
+
+    /// Create a type representing the unit type `()`.
+    pub fn unit_ty() -> Ty {
+        Ty::new_tuple(&[])
+    }
+
     /// Create a type representing a signed integer.
     pub fn signed_ty(inner: IntTy) -> Ty {
         Ty::from_rigid_kind(RigidTy::Int(inner))
@@ -93,10 +107,204 @@ pub fn unsigned_ty(inner: UintTy) -> Ty {
         Ty::from_rigid_kind(RigidTy::Uint(inner))
     }
 
+    /// Get the numeric kind of an integer or floating point type.
+    ///
+    /// Returns `None` for any other type.
+    pub fn numeric_kind(&self) -> Option<NumericKind> {
+        let kind = match self.kind().rigid()? {
+            RigidTy::Int(IntTy::Isize) => NumericKind::Isize,
+            RigidTy::Int(IntTy::I8) => NumericKind::I8,
+            RigidTy::Int(IntTy::I16) => NumericKind::I16,
+            RigidTy::Int(IntTy::I32) => NumericKind::I32,
+            RigidTy::Int(IntTy::I64) => NumericKind::I64,
+            RigidTy::Int(IntTy::I128) => NumericKind::I128,
+            RigidTy::Uint(UintTy::Usize) => NumericKind::Usize,
+            RigidTy::Uint(UintTy::U8) => NumericKind::U8,
+            RigidTy::Uint(UintTy::U16) => NumericKind::U16,
+            RigidTy::Uint(UintTy::U32) => NumericKind::U32,
+            RigidTy::Uint(UintTy::U64) => NumericKind::U64,
+            RigidTy::Uint(UintTy::U128) => NumericKind::U128,
+            RigidTy::Float(FloatTy::F16) => NumericKind::F16,
+            RigidTy::Float(FloatTy::F32) => NumericKind::F32,
+            RigidTy::Float(FloatTy::F64) => NumericKind::F64,
+            RigidTy::Float(FloatTy::F128) => NumericKind::F128,
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    /// Normalize this type, erasing its regions.
+    ///
+    /// This replaces the associated types that can be resolved by their definition. The `reveal`
+    /// mode controls whether opaque types, such as `impl Trait`, are also replaced by their hidden
+    /// type. Returns an error if the type cannot be normalized.
+    pub fn normalize(&self, reveal: Reveal) -> Result<Ty, Error> {
+        with(|cx| cx.normalize_ty(*self, reveal))
+    }
+
     /// Get a type layout.
     pub fn layout(self) -> Result<Layout, Error> {
         with(|cx| cx.ty_layout(self))
     }
+
+    /// Get the range of valid values of a scalar type.
+    ///
+    /// This is the range used by the compiler to find niches, e.g., `1..=255` for `NonZeroU8`.
+    /// Returns `None` if this type is not represented as a single initialized scalar, or if its
+    /// layout cannot be computed.
+    pub fn valid_range(&self) -> Option<WrappingRange> {
+        match self.layout().ok()?.shape().abi {
+            ValueAbi::Scalar(Scalar::Initialized { valid_range, .. }) => Some(valid_range),
+            _ => None,
+        }
+    }
+
+    /// Check whether a value of this type can be transmuted into the `other` type.
+    ///
+    /// Like the compiler does for `transmute`, this only checks that both types are sized and
+    /// have the same size. It does not check that the bytes of this type are valid for `other`.
+    ///
+    /// Returns an error if the layout of either type cannot be computed.
+    pub fn transmutable_to(&self, other: &Ty) -> Result<bool, Error> {
+        let src = self.layout()?.shape();
+        let dst = other.layout()?.shape();
+        Ok(!src.is_unsized() && !dst.is_unsized() && src.size == dst.size)
+    }
+
+    /// Check whether this type is zero-sized, e.g., `()`, `PhantomData<T>` or `[u8; 0]`.
+    ///
+    /// Returns an error if the layout of this type cannot be computed, e.g., if it is generic.
+    pub fn is_zst(&self) -> Result<bool, Error> {
+        let shape = self.layout()?.shape();
+        Ok(shape.is_sized() && shape.size.bits() == 0)
+    }
+
+    /// Check whether this type is uninhabited, i.e., whether no value of this type can exist.
+    ///
+    /// This includes `!`, enums without variants, and aggregates that contain an uninhabited
+    /// type. Code that produces a value of an uninhabited type is unreachable.
+    ///
+    /// Note that this ignores the visibility of fields, so a type may be uninhabited even if that
+    /// is not observable from the module where it is used.
+    pub fn is_uninhabited(&self) -> bool {
+        with(|cx| cx.ty_is_uninhabited(*self))
+    }
+
+    /// Get the type of the value produced by reading the discriminant of a value of this type,
+    /// i.e., the type of `Rvalue::Discriminant`.
+    ///
+    /// For enums, this is the integer type used to represent its discriminant, e.g., `isize` by
+    /// default. Returns `None` if this type is not rigid, e.g., if it is a type parameter.
+    pub fn discriminant_ty(&self) -> Option<Ty> {
+        match self.kind() {
+            TyKind::RigidTy(rigid_ty) => Some(rigid_ty.discriminant_ty()),
+            TyKind::Alias(..) | TyKind::Param(_) | TyKind::Bound(..) => None,
+        }
+    }
+
+    /// Get the discriminant value of every variant of an enum type, in declaration order.
+    ///
+    /// Each value is the bit pattern of the discriminant, truncated to the size of
+    /// [Ty::discriminant_ty]. For example, a discriminant of `-1` of an enum with `#[repr(i8)]` is
+    /// represented as `0xff`. Returns `None` if this type is not an enum.
+    pub fn discriminants(&self) -> Option<Vec<(VariantIdx, u128)>> {
+        with(|cx| cx.ty_discriminants(*self))
+    }
+
+    /// Get the function signature of a function definition, function pointer, or closure type.
+    ///
+    /// For function definitions, the signature is instantiated with the generic arguments of
+    /// the type. Returns `None` for any other type.
+    pub fn fn_sig(&self) -> Option<PolyFnSig> {
+        self.kind().fn_sig()
+    }
+
+    /// Deconstruct an ADT type into its definition and generic arguments.
+    ///
+    /// Returns `None` if this type is not an ADT.
+    pub fn as_adt(&self) -> Option<(AdtDef, GenericArgs)> {
+        if let TyKind::RigidTy(RigidTy::Adt(def, args)) = self.kind() {
+            Some((def, args))
+        } else {
+            None
+        }
+    }
+
+    /// Get the generic arguments of a rigid type that is instantiated from a definition, i.e., an
+    /// ADT, function definition, closure, coroutine, coroutine closure or coroutine witness.
+    ///
+    /// Returns `None` for any other type, including types whose components are types but not
+    /// generic arguments, such as tuples and references.
+    pub fn generic_args(&self) -> Option<GenericArgs> {
+        match self.kind() {
+            TyKind::RigidTy(
+                RigidTy::Adt(_, args)
+                | RigidTy::FnDef(_, args)
+                | RigidTy::Closure(_, args)
+                | RigidTy::Coroutine(_, args, _)
+                | RigidTy::CoroutineClosure(_, args)
+                | RigidTy::CoroutineWitness(_, args),
+            ) => Some(args),
+            _ => None,
+        }
+    }
+
+    /// Check whether this is an ADT that contains itself, i.e., whether it can be reached from
+    /// one of its fields, such as `struct List { next: Option<Box<List>> }`.
+    ///
+    /// This is computed over the field declarations of each ADT, regardless of its generic
+    /// arguments. Returns `false` for types that are not ADTs.
+    pub fn is_recursive(&self) -> bool {
+        match self.kind() {
+            TyKind::RigidTy(RigidTy::Adt(def, _)) => graph::is_recursive(def),
+            _ => false,
+        }
+    }
+
+    /// Describe the trait upcasting coercion from this trait object to the `target` one.
+    ///
+    /// Both types must be trait objects, such as the pointees of an unsizing cast from `&dyn Sub`
+    /// to `&dyn Super`. Returns `None` if either type has no principal trait, or if both have the
+    /// same principal trait, in which case there is no upcast.
+    pub fn trait_upcast(&self, target: Ty) -> Option<TraitUpcast> {
+        let source = self.kind().trait_principal()?;
+        let target = target.kind().trait_principal()?;
+        (source != target).then_some(TraitUpcast { source, target })
+    }
+
+    /// Find the slot in the vtable of this trait object that holds the vtable of `target`.
+    ///
+    /// This is used to adjust the vtable pointer when upcasting this trait object to `target`.
+    /// Returns `Ok(None)` if the vtable of this trait object can be reused as is. Both types must be
+    /// monomorphic trait objects, and the principal trait of `target` must be a supertrait of
+    /// the principal trait of this type.
+    pub fn supertrait_vtable_slot(&self, target: Ty) -> Result<Option<usize>, Error> {
+        with(|cx| cx.supertrait_vtable_slot(*self, target))
+    }
+
+    /// Get the mutability of a reference or raw pointer type.
+    ///
+    /// Returns `None` for any other type.
+    pub fn ref_mutability(&self) -> Option<Mutability> {
+        match self.kind() {
+            TyKind::RigidTy(RigidTy::Ref(_, _, mutability) | RigidTy::RawPtr(_, mutability)) => {
+                Some(mutability)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether this is the unit type `()`.
+    pub fn is_unit(&self) -> bool {
+        self.kind().is_unit()
+    }
+
+    /// Get the types of the fields of a tuple type.
+    ///
+    /// The unit type `()` is a tuple without any field. Returns `None` for any other type.
+    pub fn tuple_fields(&self) -> Option<Vec<Ty>> {
+        if let TyKind::RigidTy(RigidTy::Tuple(fields)) = self.kind() { Some(fields) } else { None }
+    }
 }
 
 impl Ty {
@@ -105,10 +313,18 @@ pub fn kind(&self) -> TyKind {
     }
 }
 
-/// Represents a pattern in the type system
+/// Represents a pattern in the type system, such as the `1..=10` in `u32 is 1..=10`.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub enum Pattern {
-    Range { start: Option<TyConst>, end: Option<TyConst>, include_end: bool },
+    /// A range of values the type is restricted to.
+    Range {
+        /// The lower bound of the range, if any.
+        start: Option<TyConst>,
+        /// The upper bound of the range, if any.
+        end: Option<TyConst>,
+        /// Whether the upper bound is included in the range, i.e., `..=` vs `..`.
+        include_end: bool,
+    },
 }
 
 /// Represents a constant in the type system
@@ -185,6 +401,26 @@ pub fn eval_target_usize(&self) -> Result<u64, Error> {
         with(|cx| cx.eval_target_usize(self))
     }
 
+    /// Get the bits of a scalar constant, together with its size in bytes.
+    ///
+    /// This works for integers, floats, booleans and chars. Floats are returned as their IEEE-754
+    /// bit pattern, and signed integers are not sign extended.
+    ///
+    /// Returns an error if the constant is not a scalar, or if it hasn't been evaluated.
+    pub fn to_bits(&self) -> Result<(u128, usize), Error> {
+        let ty_kind = self.ty.kind();
+        if !(ty_kind.is_integral() || ty_kind.is_float() || ty_kind.is_bool() || ty_kind.is_char())
+        {
+            return Err(error!("Expected a scalar constant, but found type `{ty_kind:?}`"));
+        }
+        let alloc = match &self.kind {
+            ConstantKind::Allocated(alloc)
+            | ConstantKind::Ty(TyConst { kind: TyConstKind::Value(_, alloc), .. }) => alloc,
+            kind => return Err(error!("Expected an evaluated constant, but found `{kind:?}`")),
+        };
+        Ok((alloc.read_uint()?, alloc.bytes.len()))
+    }
+
     /// Create a constant that represents a new zero-sized constant of type T.
     /// Fails if the type is not a ZST or if it doesn't have a known size.
     pub fn try_new_zero_sized(ty: Ty) -> Result<MirConst, Error> {
@@ -277,6 +513,21 @@ pub fn get_lines(&self) -> LineInfo {
         with(|c| c.get_lines(self))
     }
 
+    /// Return the span of the outermost macro call that expanded into this span.
+    ///
+    /// Return this span itself if it doesn't come from a macro expansion.
+    pub fn source_callsite(&self) -> Span {
+        with(|c| c.span_source_callsite(*self))
+    }
+
+    /// Return the chain of macro expansions that produced this span.
+    ///
+    /// The innermost expansion comes first, and the last one is the expansion of the macro
+    /// called in the original source. This is empty if the span doesn't come from a macro.
+    pub fn macro_backtrace(&self) -> Vec<ExpnInfo> {
+        with(|c| c.span_macro_backtrace(*self))
+    }
+
     /// Return the span location to be printed in diagnostic messages.
     ///
     /// This may leak local file paths and should not be used to build artifacts that may be
@@ -296,6 +547,29 @@ pub struct LineInfo {
     pub end_col: usize,
 }
 
+/// Information about a macro expansion.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ExpnInfo {
+    /// The name of the macro, e.g., `println` or `derive`.
+    pub macro_name: Symbol,
+    pub kind: MacroKind,
+    /// The span of the macro invocation.
+    pub call_site: Span,
+    /// The span of the macro definition.
+    pub def_site: Span,
+}
+
+/// The kind of a macro invocation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize)]
+pub enum MacroKind {
+    /// A bang macro, e.g., `foo!()`.
+    Bang,
+    /// An attribute macro, e.g., `#[foo]`.
+    Attr,
+    /// A derive macro, e.g., `#[derive(Foo)]`.
+    Derive,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub enum TyKind {
     RigidTy(RigidTy),
@@ -314,6 +588,11 @@ pub fn is_unit(&self) -> bool {
         matches!(self, TyKind::RigidTy(RigidTy::Tuple(data)) if data.is_empty())
     }
 
+    #[inline]
+    pub fn is_never(&self) -> bool {
+        matches!(self, TyKind::RigidTy(RigidTy::Never))
+    }
+
     #[inline]
     pub fn is_bool(&self) -> bool {
         matches!(self, TyKind::RigidTy(RigidTy::Bool))
@@ -596,6 +875,27 @@ pub fn num_bytes(self) -> usize {
             IntTy::I128 => 16,
         }
     }
+
+    /// The largest value representable by this type in the given machine.
+    pub fn max_value(self, machine: &MachineInfo) -> i128 {
+        i128::MAX >> (128 - self.num_bits(machine))
+    }
+
+    /// The smallest value representable by this type in the given machine.
+    pub fn min_value(self, machine: &MachineInfo) -> i128 {
+        i128::MIN >> (128 - self.num_bits(machine))
+    }
+
+    fn num_bits(self, machine: &MachineInfo) -> usize {
+        match self {
+            IntTy::Isize => machine.pointer_width.bits(),
+            IntTy::I8 => 8,
+            IntTy::I16 => 16,
+            IntTy::I32 => 32,
+            IntTy::I64 => 64,
+            IntTy::I128 => 128,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
@@ -619,6 +919,28 @@ pub fn num_bytes(self) -> usize {
             UintTy::U128 => 16,
         }
     }
+
+    /// The largest value representable by this type in the given machine.
+    pub fn max_value(self, machine: &MachineInfo) -> u128 {
+        u128::MAX >> (128 - self.num_bits(machine))
+    }
+
+    /// The smallest value representable by this type, which is always zero.
+    pub fn min_value(self, machine: &MachineInfo) -> u128 {
+        let _ = machine;
+        0
+    }
+
+    fn num_bits(self, machine: &MachineInfo) -> usize {
+        match self {
+            UintTy::Usize => machine.pointer_width.bits(),
+            UintTy::U8 => 8,
+            UintTy::U16 => 16,
+            UintTy::U32 => 32,
+            UintTy::U64 => 64,
+            UintTy::U128 => 128,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
@@ -629,6 +951,68 @@ pub enum FloatTy {
     F128,
 }
 
+/// Whether normalization and constant evaluation see through opaque types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum Reveal {
+    /// Opaque types are kept as is, as seen by the user of a function that returns `impl Trait`.
+    UserFacing,
+    /// Opaque types are replaced by their hidden type, as done after type checking.
+    All,
+}
+
+/// A flat representation of the signed integer, unsigned integer and floating point types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum NumericKind {
+    Isize,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Usize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    F16,
+    F32,
+    F64,
+    F128,
+}
+
+impl NumericKind {
+    /// The number of bits of this type in the given machine.
+    pub fn bit_width(self, machine: &MachineInfo) -> usize {
+        match self {
+            NumericKind::Isize | NumericKind::Usize => machine.pointer_width.bits(),
+            NumericKind::I8 | NumericKind::U8 => 8,
+            NumericKind::I16 | NumericKind::U16 | NumericKind::F16 => 16,
+            NumericKind::I32 | NumericKind::U32 | NumericKind::F32 => 32,
+            NumericKind::I64 | NumericKind::U64 | NumericKind::F64 => 64,
+            NumericKind::I128 | NumericKind::U128 | NumericKind::F128 => 128,
+        }
+    }
+
+    /// Whether this is a signed integer type.
+    pub fn is_signed_int(self) -> bool {
+        matches!(
+            self,
+            NumericKind::Isize
+                | NumericKind::I8
+                | NumericKind::I16
+                | NumericKind::I32
+                | NumericKind::I64
+                | NumericKind::I128
+        )
+    }
+
+    /// Whether this is a floating point type.
+    pub fn is_float(self) -> bool {
+        matches!(self, NumericKind::F16 | NumericKind::F32 | NumericKind::F64 | NumericKind::F128)
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 pub enum Movability {
     Static,
@@ -741,6 +1125,22 @@ fn from(def: IntrinsicDef) -> Self {
     pub ClosureDef;
 }
 
+impl ClosureDef {
+    /// Retrieve the item where this closure is defined.
+    ///
+    /// For a closure nested inside another closure, this is the outer closure.
+    pub fn parent(&self) -> CrateItem {
+        with(|cx| cx.closure_parent(*self))
+    }
+
+    /// Check whether this closure captures no variable for the given generic arguments.
+    ///
+    /// Only closures that capture nothing can be coerced to a function pointer.
+    pub fn is_non_capturing(&self, args: &GenericArgs) -> bool {
+        with(|cx| cx.closure_upvar_tys(args)).is_empty()
+    }
+}
+
 crate_def! {
     #[derive(Serialize)]
     pub CoroutineDef;
@@ -798,11 +1198,39 @@ pub fn is_simd(&self) -> bool {
         with(|cx| cx.adt_is_simd(*self))
     }
 
+    /// Whether this type has its own implementation of the `Drop` trait.
+    ///
+    /// This doesn't consider the fields of this type. For example, `Vec<T>` has a `Drop`
+    /// implementation, while a struct that only contains a `String` does not, even though
+    /// dropping it will drop the `String`.
+    pub fn has_drop_impl(&self) -> bool {
+        with(|cx| cx.adt_has_drop_impl(*self))
+    }
+
+    /// Retrieve the type of the field that determines the layout of a `#[repr(transparent)]`
+    /// type, after instantiating it with the given arguments.
+    ///
+    /// This is the only field that is not a 1-ZST, e.g., `u32` for `struct Wrapper(u32, ())`.
+    /// Returns `None` if this type is not transparent, or if all of its fields are 1-ZSTs.
+    pub fn transparent_field_ty(&self, args: &GenericArgs) -> Option<Ty> {
+        with(|cx| cx.adt_transparent_field_ty(*self, args))
+    }
+
     /// The number of variants in this ADT.
+    ///
+    /// This is cheap to compute, since it doesn't require retrieving the variants' fields.
     pub fn num_variants(&self) -> usize {
         with(|cx| cx.adt_variants_len(*self))
     }
 
+    /// Whether this is an enum without any variant, such as `enum Void {}`.
+    ///
+    /// Values of such types cannot be constructed, thus any code that matches on them is
+    /// unreachable.
+    pub fn is_empty_enum(&self) -> bool {
+        self.kind() == AdtKind::Enum && self.num_variants() == 0
+    }
+
     /// Retrieve the variants in this ADT.
     pub fn variants(&self) -> Vec<VariantDef> {
         self.variants_iter().collect()
@@ -914,6 +1342,23 @@ impl TraitDef {
     pub fn declaration(trait_def: &TraitDef) -> TraitDecl {
         with(|cx| cx.trait_decl(trait_def))
     }
+
+    /// Retrieve every implementation of this trait, together with the type it is implemented for.
+    ///
+    /// This includes the implementations from all crates. The self type of a generic
+    /// implementation refers to the parameters of that implementation, e.g., `Vec<T>` for
+    /// `impl<T> Trait for Vec<T>`.
+    pub fn impls_with_self_types(&self) -> Vec<(Ty, ImplDef)> {
+        with(|cx| {
+            cx.all_trait_impls()
+                .into_iter()
+                .filter_map(|impl_def| {
+                    let trait_ref = cx.trait_impl(&impl_def).value;
+                    (trait_ref.def_id == *self).then(|| (trait_ref.self_ty(), impl_def))
+                })
+                .collect()
+        })
+    }
 }
 
 crate_def! {
@@ -926,6 +1371,28 @@ pub fn declaration(trait_def: &TraitDef) -> TraitDecl {
     pub ConstDef;
 }
 
+impl ConstDef {
+    /// Retrieve the body that computes the value of this constant.
+    ///
+    /// This is the body used for constant evaluation, so it can be used to step through the
+    /// evaluation of the constant. This returns `None` if the body is not available.
+    pub fn initializer_body(&self) -> Option<Body> {
+        with(|cx| cx.initializer_body(self.0))
+    }
+}
+
+impl TryFrom<CrateItem> for ConstDef {
+    type Error = Error;
+
+    fn try_from(value: CrateItem) -> Result<Self, Self::Error> {
+        if matches!(value.kind(), ItemKind::Const) {
+            Ok(ConstDef(value.0))
+        } else {
+            Err(Error::new(format!("Expected a const item, but found: {value:?}")))
+        }
+    }
+}
+
 crate_def! {
     /// A trait impl definition.
     #[derive(Serialize)]
@@ -937,6 +1404,25 @@ impl ImplDef {
     pub fn trait_impl(&self) -> ImplTrait {
         with(|cx| cx.trait_impl(self))
     }
+
+    /// Retrieve the generic parameters declared by this implementation.
+    pub fn generics_of(&self) -> Generics {
+        with(|cx| cx.generics_of(self.0))
+    }
+
+    /// Check whether this is a blanket implementation, i.e., whether its self type is a bare
+    /// type parameter, such as `impl<T> Trait for T`.
+    pub fn is_blanket(&self) -> bool {
+        matches!(self.trait_impl().value.self_ty().kind(), TyKind::Param(_))
+    }
+
+    /// Evaluate the associated constant with the given name for this implementation.
+    ///
+    /// For a trait implementation that does not define the constant, this evaluates the default
+    /// value provided by the trait. This will fail if the implementation is generic.
+    pub fn associated_const(&self, name: &str) -> Result<MirConst, Error> {
+        with(|cx| cx.impl_associated_const(*self, name))
+    }
 }
 
 crate_def! {
@@ -949,6 +1435,16 @@ pub fn trait_impl(&self) -> ImplTrait {
     pub CoroutineWitnessDef;
 }
 
+impl CoroutineWitnessDef {
+    /// Retrieve the types that the coroutine holds across suspension points, i.e., the types
+    /// stored in the coroutine state.
+    ///
+    /// The `args` should be the generic arguments of the witness type.
+    pub fn witness_tys(&self, args: &GenericArgs) -> Vec<Ty> {
+        with(|cx| cx.coroutine_witness_tys(*self, args))
+    }
+}
+
 /// A list of generic arguments.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct GenericArgs(pub Vec<GenericArgKind>);
@@ -1179,6 +1675,15 @@ pub struct ExistentialTraitRef {
     pub generic_args: GenericArgs,
 }
 
+/// A trait upcasting coercion between two trait objects, such as from `dyn Sub` to `dyn Super`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct TraitUpcast {
+    /// The principal trait of the trait object being coerced.
+    pub source: Binder<ExistentialTraitRef>,
+    /// The principal trait of the resulting trait object.
+    pub target: Binder<ExistentialTraitRef>,
+}
+
 impl Binder<ExistentialTraitRef> {
     pub fn with_self_ty(&self, self_ty: Ty) -> Binder<TraitRef> {
         self.map_bound_ref(|trait_ref| trait_ref.with_self_ty(self_ty))
@@ -1186,6 +1691,19 @@ pub fn with_self_ty(&self, self_ty: Ty) -> Binder<TraitRef> {
 }
 
 impl ExistentialTraitRef {
+    /// The trait that is referenced.
+    pub fn trait_def(&self) -> TraitDef {
+        self.def_id
+    }
+
+    /// The generic arguments of the trait, without `Self`.
+    ///
+    /// Associated type bindings, such as `Item = u8` in `dyn Iterator<Item = u8>`, are not
+    /// included. Those are represented by [ExistentialPredicate::Projection].
+    pub fn args(&self) -> &GenericArgs {
+        &self.generic_args
+    }
+
     pub fn with_self_ty(&self, self_ty: Ty) -> TraitRef {
         TraitRef::new(self.def_id, self_ty, &self.generic_args)
     }
@@ -1200,6 +1718,9 @@ pub struct ExistentialProjection {
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct ParamTy {
+    /// The index of this parameter in the generics of the item that declares it.
+    ///
+    /// Inside of a trait and its associated items, `Self` is the parameter with index `0`.
     pub index: u32,
     pub name: String,
 }
@@ -1218,7 +1739,28 @@ pub struct BoundTy {
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize)]
 pub struct Prov(pub AllocId);
 
-pub type Align = u64;
+/// An alignment in bytes, which is always a power of two.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize)]
+pub struct Align(u64);
+
+impl Align {
+    /// Create an alignment of `bytes` bytes.
+    ///
+    /// Returns an error if `bytes` is not a power of two.
+    pub fn from_bytes(bytes: u64) -> Result<Align, Error> {
+        if bytes.is_power_of_two() {
+            Ok(Align(bytes))
+        } else {
+            Err(error!("Expected alignment to be a power of two, but found `{bytes}`"))
+        }
+    }
+
+    /// The alignment in bytes.
+    pub fn bytes(self) -> u64 {
+        self.0
+    }
+}
+
 pub type Promoted = u32;
 pub type InitMaskMaterialized = Vec<u64>;
 
@@ -1239,6 +1781,36 @@ pub struct Allocation {
 }
 
 impl Allocation {
+    /// Whether this allocation can be modified, e.g., the memory of a `static mut`.
+    ///
+    /// Writes to an immutable allocation are undefined behavior.
+    pub fn mutability(&self) -> Mutability {
+        self.mutability
+    }
+
+    /// The alignment of this allocation.
+    pub fn align(&self) -> Align {
+        self.align
+    }
+
+    /// Check whether all bytes in the given range are initialized.
+    ///
+    /// Padding bytes are uninitialized, and they must not be read as defined values.
+    /// Returns an error if the range is reversed or out of bounds.
+    pub fn is_init(&self, range: Range<usize>) -> Result<bool, Error> {
+        if range.start > range.end {
+            return Err(error!("Invalid range `{range:?}`: start is greater than end"));
+        }
+        if range.end > self.bytes.len() {
+            return Err(error!(
+                "Range is out of bounds. Allocation length is `{}`, but requested range `{:?}`",
+                self.bytes.len(),
+                range
+            ));
+        }
+        Ok(self.bytes[range].iter().all(Option::is_some))
+    }
+
     /// Get a vector of bytes for an Allocation that has been fully initialized
     pub fn raw_bytes(&self) -> Result<Vec<u8>, Error> {
         self.bytes
@@ -1308,9 +1880,13 @@ pub fn is_null(&self) -> Result<bool, Error> {
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub enum ConstantKind {
+    /// A constant from the type system, such as an array length.
     Ty(TyConst),
+    /// An evaluated constant, with the bytes of its value.
     Allocated(Allocation),
+    /// A constant that still needs to be evaluated, such as an associated constant.
     Unevaluated(UnevaluatedConst),
+    /// A use of a const generic parameter.
     Param(ParamConst),
     /// Store ZST constants.
     /// We have to special handle these constants since its type might be generic.
@@ -1330,6 +1906,24 @@ pub struct UnevaluatedConst {
     pub promoted: Option<Promoted>,
 }
 
+impl UnevaluatedConst {
+    /// Evaluate this constant in a concrete context.
+    ///
+    /// The generic arguments of this constant may refer to the generic parameters of the body
+    /// where it is used. Those parameters are instantiated with `args`, which should be the
+    /// arguments of that body, e.g., the arguments of the `Instance` being analyzed.
+    ///
+    /// The `reveal` mode controls whether opaque types are seen through when resolving the
+    /// constant and computing its type. For example, an associated constant of an `impl Trait`
+    /// type can only be resolved with [Reveal::All].
+    ///
+    /// Returns an error if the constant is still too generic after the instantiation, or if
+    /// its evaluation fails.
+    pub fn eval(&self, args: &GenericArgs, reveal: Reveal) -> Result<MirConst, Error> {
+        with(|cx| cx.eval_unevaluated_const(self, args, reveal))
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 pub enum TraitSpecializationKind {
     None,
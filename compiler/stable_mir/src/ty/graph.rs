@@ -0,0 +1,118 @@
+//! Dependency graph between algebraic data types.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::ControlFlow;
+
+use crate::CrateItem;
+use crate::compiler_interface::with;
+use crate::ty::{AdtDef, RigidTy, Ty, TyKind};
+use crate::visitor::{Visitable, Visitor};
+
+/// A graph that maps each ADT to the ADTs used by the types of its fields.
+#[derive(Clone, Debug, Default)]
+#[allow(rustc::default_hash_types)]
+pub struct TypeGraph {
+    edges: HashMap<AdtDef, Vec<AdtDef>>,
+}
+
+impl TypeGraph {
+    /// The ADTs used by the fields of `adt`, in the order they are first found.
+    ///
+    /// This is empty if `adt` is not part of this graph.
+    pub fn successors(&self, adt: AdtDef) -> &[AdtDef] {
+        self.edges.get(&adt).map_or(&[], Vec::as_slice)
+    }
+
+    /// Whether the fields of `from` use the type `to`.
+    pub fn has_edge(&self, from: AdtDef, to: AdtDef) -> bool {
+        self.successors(from).contains(&to)
+    }
+
+    /// Iterate over all the ADTs in this graph.
+    pub fn nodes(&self) -> impl Iterator<Item = AdtDef> + '_ {
+        self.edges.keys().copied()
+    }
+}
+
+/// Build the graph of the ADTs used by the given items.
+///
+/// The graph starts from the ADTs used by the type of each item, and by the locals of its body,
+/// if there is one. It then includes every ADT reachable through the fields of any variant.
+/// Each ADT is only visited once, so recursive types are supported.
+pub fn type_graph(items: &[CrateItem]) -> TypeGraph {
+    let mut worklist = VecDeque::new();
+    for item in items {
+        worklist.extend(collect_adts(&item.ty()));
+        if with(|cx| cx.has_body(item.0)) {
+            for local in item.body().locals() {
+                worklist.extend(collect_adts(&local.ty));
+            }
+        }
+    }
+
+    let mut graph = TypeGraph::default();
+    while let Some(adt) = worklist.pop_front() {
+        if graph.edges.contains_key(&adt) {
+            continue;
+        }
+        let successors = field_adts(adt);
+        worklist.extend(successors.iter().copied());
+        graph.edges.insert(adt, successors);
+    }
+    graph
+}
+
+/// Whether `adt` can be reached from itself through the fields of the ADTs it uses.
+///
+/// Unlike [type_graph], this stops as soon as a cycle back to `adt` is found.
+#[allow(rustc::default_hash_types)]
+pub(super) fn is_recursive(adt: AdtDef) -> bool {
+    let mut visited = HashSet::new();
+    let mut worklist = VecDeque::from(field_adts(adt));
+    while let Some(next) = worklist.pop_front() {
+        if next == adt {
+            return true;
+        }
+        if visited.insert(next) {
+            worklist.extend(field_adts(next));
+        }
+    }
+    false
+}
+
+/// Collect the ADTs used by the fields of any variant of `adt`, in the order they are found.
+fn field_adts(adt: AdtDef) -> Vec<AdtDef> {
+    let mut adts = Vec::new();
+    for field in adt.variants_iter().flat_map(|variant| variant.fields()) {
+        for used in collect_adts(&field.ty()) {
+            if !adts.contains(&used) {
+                adts.push(used);
+            }
+        }
+    }
+    adts
+}
+
+/// Collect the ADTs used by the given type, in the order they are found.
+fn collect_adts(ty: &Ty) -> Vec<AdtDef> {
+    let mut collector = AdtCollector { adts: Vec::new() };
+    let _ = ty.visit(&mut collector);
+    collector.adts
+}
+
+struct AdtCollector {
+    adts: Vec<AdtDef>,
+}
+
+impl Visitor for AdtCollector {
+    type Break = ();
+
+    fn visit_ty(&mut self, ty: &Ty) -> ControlFlow<Self::Break> {
+        if let TyKind::RigidTy(RigidTy::Adt(def, _)) = ty.kind() {
+            if !self.adts.contains(&def) {
+                self.adts.push(def);
+            }
+        }
+        ty.super_visit(self)
+    }
+}
@@ -2,7 +2,7 @@
 
 use super::ty::{
     Allocation, Binder, ConstDef, ExistentialPredicate, FnSig, GenericArgKind, GenericArgs,
-    MirConst, Promoted, Region, RigidTy, TermKind, Ty, UnevaluatedConst,
+    MirConst, Pattern, Promoted, Region, RigidTy, TermKind, Ty, UnevaluatedConst,
 };
 use crate::Opaque;
 use crate::ty::TyConst;
@@ -18,6 +18,13 @@ fn visit_const(&mut self, c: &TyConst) -> ControlFlow<Self::Break> {
     fn visit_reg(&mut self, reg: &Region) -> ControlFlow<Self::Break> {
         reg.super_visit(self)
     }
+    /// Whether to also visit the types that a coroutine witness holds across suspension points.
+    ///
+    /// These types are not part of the witness type itself, and computing them requires querying
+    /// the compiler, so they are only visited by visitors that opt in.
+    fn expand_coroutine_witness(&self) -> bool {
+        false
+    }
 }
 
 pub trait Visitable {
@@ -142,6 +149,22 @@ fn super_visit<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
     }
 }
 
+impl Visitable for Pattern {
+    fn super_visit<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
+        match self {
+            Pattern::Range { start, end, include_end: _ } => {
+                if let Some(start) = start {
+                    start.visit(visitor)?;
+                }
+                if let Some(end) = end {
+                    end.visit(visitor)?;
+                }
+                ControlFlow::Continue(())
+            }
+        }
+    }
+}
+
 impl Visitable for RigidTy {
     fn super_visit<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
         match self {
@@ -157,7 +180,10 @@ fn super_visit<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
                 t.visit(visitor)?;
                 c.visit(visitor)
             }
-            RigidTy::Pat(t, _p) => t.visit(visitor),
+            RigidTy::Pat(t, p) => {
+                t.visit(visitor)?;
+                p.visit(visitor)
+            }
             RigidTy::Slice(inner) => inner.visit(visitor),
             RigidTy::RawPtr(ty, _) => ty.visit(visitor),
             RigidTy::Ref(reg, ty, _) => {
@@ -167,9 +193,15 @@ fn super_visit<V: Visitor>(&self, visitor: &mut V) -> ControlFlow<V::Break> {
             RigidTy::Adt(_, args)
             | RigidTy::Closure(_, args)
             | RigidTy::Coroutine(_, args, _)
-            | RigidTy::CoroutineWitness(_, args)
             | RigidTy::CoroutineClosure(_, args)
             | RigidTy::FnDef(_, args) => args.visit(visitor),
+            RigidTy::CoroutineWitness(def, args) => {
+                args.visit(visitor)?;
+                if visitor.expand_coroutine_witness() {
+                    def.witness_tys(args).visit(visitor)?;
+                }
+                ControlFlow::Continue(())
+            }
             RigidTy::FnPtr(sig) => sig.visit(visitor),
             RigidTy::Dynamic(pred, r, _) => {
                 pred.visit(visitor)?;
@@ -0,0 +1,104 @@
+//@ run-pass
+//! Test that users are able to query the number of variants of an ADT.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{AdtDef, AdtKind, RigidTy, TyKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let check = get_item(&items, "check").unwrap();
+    let body = check.body();
+    let adts: Vec<_> = body.arg_locals().iter().map(|local| expect_adt(local.ty.kind())).collect();
+    let [void, single, triple, unit_struct] = adts[..] else { unreachable!() };
+
+    assert_eq!(void.kind(), AdtKind::Enum);
+    assert_eq!(void.num_variants(), 0);
+    assert!(void.is_empty_enum());
+
+    assert_eq!(single.num_variants(), 1);
+    assert!(!single.is_empty_enum());
+
+    assert_eq!(triple.num_variants(), 3);
+    assert!(!triple.is_empty_enum());
+
+    // Structs always have exactly one variant.
+    assert_eq!(unit_struct.kind(), AdtKind::Struct);
+    assert_eq!(unit_struct.num_variants(), 1);
+    assert!(!unit_struct.is_empty_enum());
+    ControlFlow::Continue(())
+}
+
+fn expect_adt(kind: TyKind) -> AdtDef {
+    let TyKind::RigidTy(RigidTy::Adt(def, _)) = kind else {
+        unreachable!("Expected an ADT, but found: {kind:?}")
+    };
+    def
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "adt_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub enum Void {{}}
+
+    pub enum Single {{
+        Only(u8),
+    }}
+
+    pub enum Triple {{
+        A,
+        B(u32),
+        C {{ x: u64 }},
+    }}
+
+    pub struct Unit;
+
+    pub fn check(_: Void, _: Single, _: Triple, _: Unit) {{}}
+    "#
+    )?;
+    Ok(())
+}
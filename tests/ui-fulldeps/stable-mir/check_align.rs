@@ -0,0 +1,74 @@
+//@ run-pass
+//! Test that users are able to create alignments and retrieve them from layouts.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{Align, Ty, UintTy};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    assert!(Align::from_bytes(3).is_err());
+    assert!(Align::from_bytes(0).is_err());
+    assert_eq!(Align::from_bytes(8).unwrap().bytes(), 8);
+    assert!(Align::from_bytes(1).unwrap() < Align::from_bytes(2).unwrap());
+
+    let layout = Ty::unsigned_ty(UintTy::U16).layout().unwrap().shape();
+    assert_eq!(layout.abi_align, Align::from_bytes(2).unwrap());
+
+    let items = stable_mir::all_local_items();
+    let body = items.iter().find(|item| item.trimmed_name() == "aligned").unwrap().body();
+    let layout = body.arg_locals()[0].ty.layout().unwrap().shape();
+    assert_eq!(layout.abi_align.bytes(), 16);
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "align_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #[repr(align(16))]
+    pub struct Aligned(u8);
+
+    pub fn aligned(_a: Aligned) {{}}
+    "#
+    )?;
+    Ok(())
+}
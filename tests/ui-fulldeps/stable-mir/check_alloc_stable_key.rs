@@ -0,0 +1,94 @@
+//@ run-pass
+//! Test that users are able to build ordered allocation tables that don't depend on
+//! the order in which the compiler created the allocations.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::alloc::AllocKey;
+use stable_mir::mir::mono::StaticDef;
+use stable_mir::*;
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// Collect the allocations reachable from the given statics, in the given order, into a table
+/// keyed by their stable key.
+fn alloc_table(names: &[&str]) -> ControlFlow<(), Vec<AllocKey>> {
+    let items = stable_mir::all_local_items();
+    let mut table = BTreeMap::new();
+    for name in names {
+        for (id, global_alloc) in get_static(&items, name).reachable_allocs().unwrap() {
+            let key = id.stable_key();
+            assert_eq!(key, id.stable_key());
+            table.insert(key, global_alloc);
+        }
+    }
+    ControlFlow::Continue(table.into_keys().collect())
+}
+
+fn get_static(items: &CrateItems, name: &str) -> StaticDef {
+    let item = items.iter().find(|crate_item| crate_item.trimmed_name() == name).unwrap();
+    StaticDef::try_from(item).unwrap()
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "alloc_stable_key_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    // Evaluate the statics in a different order in each run, so the allocations are created in
+    // a different order as well.
+    let first = run!(args.clone(), || alloc_table(&["BYTES", "TEXT", "CALLBACK"])).unwrap();
+    let second = run!(args, || alloc_table(&["CALLBACK", "TEXT", "BYTES"])).unwrap();
+    assert_eq!(first.len(), 5, "Unexpected keys: {first:?}");
+    assert_eq!(first, second);
+    assert!(first.iter().any(|key| key.as_str().starts_with("fn ")));
+    assert!(first.iter().any(|key| key.as_str().starts_with("static ")));
+    // Memory allocations are keyed by a digest of their content, so the size of their keys
+    // doesn't depend on the size of the allocations.
+    let memory: Vec<_> = first.iter().filter(|key| key.as_str().starts_with("memory ")).collect();
+    assert_eq!(memory.len(), 3, "Unexpected keys: {first:?}");
+    assert!(memory.iter().any(|key| key.as_str().contains(" size 5 ")), "{memory:?}");
+    assert!(memory.iter().all(|key| key.as_str().len() < 64), "{memory:?}");
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub static BYTES: &[u8] = &[1, 2, 3];
+    pub static TEXT: &str = "hello";
+    pub static CALLBACK: (&u8, fn(), &&[u8]) = (&7, callback, &BYTES);
+
+    pub fn callback() {{}}
+    "#
+    )?;
+    Ok(())
+}
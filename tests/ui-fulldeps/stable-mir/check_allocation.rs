@@ -18,13 +18,14 @@
 extern crate rustc_smir;
 extern crate rustc_driver;
 extern crate rustc_interface;
+extern crate serde_json;
 extern crate stable_mir;
 
 use rustc_smir::rustc_internal;
 use stable_mir::crate_def::CrateDef;
-use stable_mir::mir::alloc::GlobalAlloc;
+use stable_mir::mir::alloc::{AllocId, GlobalAlloc};
 use stable_mir::mir::mono::{Instance, InstanceKind, StaticDef};
-use stable_mir::mir::{Body, TerminatorKind};
+use stable_mir::mir::{Body, Mutability, TerminatorKind};
 use stable_mir::ty::{Allocation, ConstantKind, RigidTy, TyKind};
 use stable_mir::{CrateItem, CrateItems, ItemKind};
 use std::ascii::Char;
@@ -33,7 +34,7 @@
 use std::collections::HashMap;
 use std::ffi::CStr;
 use std::io::Write;
-use std::ops::ControlFlow;
+use std::ops::{ControlFlow, Range};
 
 const CRATE_NAME: &str = "input";
 
@@ -45,6 +46,13 @@ fn test_stable_mir() -> ControlFlow<()> {
     check_bar(*get_item(&items, (ItemKind::Static, "BAR")).unwrap());
     check_len(*get_item(&items, (ItemKind::Static, "LEN")).unwrap());
     check_cstr(*get_item(&items, (ItemKind::Static, "C_STR")).unwrap());
+    check_dup(*get_item(&items, (ItemKind::Static, "DUP")).unwrap());
+    check_dup_consts(*get_item(&items, (ItemKind::Fn, "dup_consts")).unwrap());
+    check_padding(*get_item(&items, (ItemKind::Static, "PADDED")).unwrap());
+    check_mutability(
+        *get_item(&items, (ItemKind::Static, "LEN")).unwrap(),
+        *get_item(&items, (ItemKind::Static, "COUNTER")).unwrap(),
+    );
     check_other_consts(*get_item(&items, (ItemKind::Fn, "other_consts")).unwrap());
     check_type_id(*get_item(&items, (ItemKind::Fn, "check_type_id")).unwrap());
     ControlFlow::Continue(())
@@ -104,6 +112,99 @@ fn check_cstr(item: CrateItem) {
     assert_eq!(CStr::from_bytes_until_nul(&allocation.raw_bytes().unwrap()), Ok(c"cstr"));
 }
 
+/// Check that allocations referenced by static `DUP` can be deduplicated by their identity.
+///
+/// ```no_run
+/// static DUP: [&str; 2] = [BAR, BAR];
+/// ```
+fn check_dup(item: CrateItem) {
+    let def = StaticDef::try_from(item).unwrap();
+    let alloc = def.eval_initializer().unwrap();
+    assert_eq!(alloc.provenance.ptrs.len(), 2);
+
+    let alloc_id_0 = alloc.provenance.ptrs[0].1.0;
+    let alloc_id_1 = alloc.provenance.ptrs[1].1.0;
+    assert!(alloc_id_0.same_allocation(&alloc_id_1));
+
+    let mut alloc_table: Vec<(AllocId, GlobalAlloc)> = vec![];
+    for (_, prov) in &alloc.provenance.ptrs {
+        if !alloc_table.iter().any(|(alloc_id, _)| alloc_id.same_allocation(&prov.0)) {
+            alloc_table.push((prov.0, GlobalAlloc::from(prov.0)));
+        }
+    }
+    assert_eq!(alloc_table.len(), 1);
+    let json = serde_json::to_string(&alloc_table).expect("serde_json failed");
+    assert_eq!(json.matches("Memory").count(), 1, "Unexpected table: {json}");
+}
+
+/// Check that distinct allocation IDs are compared by the allocation they point to.
+///
+/// ```no_run
+/// fn dup_consts() {
+///     let _first = &[1u8, 2];
+///     let _second = &[1u8, 2];
+///     let _other = &[3u8, 4];
+/// }
+/// ```
+fn check_dup_consts(item: CrateItem) {
+    // Instance body will force constant evaluation.
+    let body = Instance::try_from(item).unwrap().body().unwrap();
+    let consts = collect_consts(&body);
+    let pointee = |name: &str| {
+        let alloc = consts[name];
+        assert_eq!(alloc.provenance.ptrs.len(), 1, "Unexpected allocation: {alloc:?}");
+        alloc.provenance.ptrs[0].1.0
+    };
+    let first = pointee("_first");
+    let second = pointee("_second");
+    let other = pointee("_other");
+
+    // Each promoted constant gets its own allocation ID, even if the memory is the same.
+    assert_ne!(first, second);
+    assert!(first.same_allocation(&second));
+    assert!(!first.same_allocation(&other));
+    assert!(!other.same_allocation(&second));
+}
+
+/// Check that padding bytes in static `PADDED` are reported as uninitialized.
+///
+/// ```no_run
+/// #[repr(C)]
+/// struct Padded { a: u8, b: u32 }
+/// static PADDED: Padded = Padded { a: 1, b: 2 };
+/// ```
+fn check_padding(item: CrateItem) {
+    let def = StaticDef::try_from(item).unwrap();
+    let alloc = def.eval_initializer().unwrap();
+    assert_eq!(alloc.bytes.len(), 8);
+    assert_eq!(alloc.is_init(0..1), Ok(true));
+    assert_eq!(alloc.is_init(1..4), Ok(false));
+    assert_eq!(alloc.is_init(4..8), Ok(true));
+    assert_eq!(alloc.is_init(0..8), Ok(false));
+    assert!(alloc.is_init(4..9).is_err());
+    assert!(alloc.is_init(Range { start: 4, end: 1 }).is_err());
+
+    assert!(alloc.raw_bytes().is_err());
+    assert_eq!(alloc.read_partial_uint(0..1), Ok(1));
+    assert_eq!(alloc.read_partial_uint(4..8), Ok(2));
+}
+
+/// Check the mutability and alignment of the allocations of a `static` and a `static mut`.
+///
+/// ```no_run
+/// static LEN: usize = 2;
+/// static mut COUNTER: u16 = 0;
+/// ```
+fn check_mutability(immutable: CrateItem, mutable: CrateItem) {
+    let alloc = StaticDef::try_from(immutable).unwrap().eval_initializer().unwrap();
+    assert_eq!(alloc.mutability(), Mutability::Not);
+    assert_eq!(alloc.align().bytes(), alloc.bytes.len() as u64);
+
+    let alloc = StaticDef::try_from(mutable).unwrap().eval_initializer().unwrap();
+    assert_eq!(alloc.mutability(), Mutability::Mut);
+    assert_eq!(alloc.align().bytes(), 2);
+}
+
 /// Check the allocation data for constants used in `other_consts` function.
 fn check_other_consts(item: CrateItem) {
     // Instance body will force constant evaluation.
@@ -243,6 +344,15 @@ fn generate_input(path: &str) -> std::io::Result<()> {
     static FOO: [&str; 2] = ["hi", "there"];
     static BAR: &str = "Bar";
     static C_STR: &std::ffi::CStr = c"cstr";
+    static DUP: [&str; 2] = [BAR, BAR];
+    static mut COUNTER: u16 = 0;
+
+    #[repr(C)]
+    struct Padded {{
+        a: u8,
+        b: u32,
+    }}
+    static PADDED: Padded = Padded {{ a: 1, b: 2 }};
     const NULL: *const u8 = std::ptr::null();
     const TUPLE: (u32, u32) = (10, u32::MAX);
 
@@ -258,6 +368,12 @@ fn other_consts() {{
         let _tuple = TUPLE;
     }}
 
+    fn dup_consts() {{
+        let _first = &[1u8, 2];
+        let _second = &[1u8, 2];
+        let _other = &[3u8, 4];
+    }}
+
     fn check_type_id() {{
         let _char_id = type_id::<char>();
         let _bool_id = type_id::<bool>();
@@ -267,6 +383,7 @@ pub fn main() {{
         println!("{{FOO:?}}! {{BAR}}");
         assert_eq!(FOO.len(), LEN);
         other_consts();
+        dup_consts();
     }}"#
     )?;
     Ok(())
@@ -0,0 +1,86 @@
+//@ run-pass
+//! Test that users are able to retrieve the ABI attributes of function arguments.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::abi::{ArgAttributes, PassMode};
+use stable_mir::mir::mono::Instance;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let item = *items.iter().find(|item| item.trimmed_name() == "read").unwrap();
+    let fn_abi = Instance::try_from(item).unwrap().fn_abi().unwrap();
+    assert_eq!(fn_abi.args.len(), 2);
+
+    // Shared references to frozen data do not alias, and they point to valid memory.
+    let attrs = direct_attrs(&fn_abi.args[0].mode);
+    assert!(attrs.no_alias);
+    assert!(attrs.read_only);
+    assert!(attrs.non_null);
+    assert!(attrs.is_dereferenceable());
+    assert_eq!(attrs.pointee_size.bytes(), 1);
+    assert_eq!(attrs.pointee_align.map(|align| align.bytes()), Some(1));
+
+    // Raw pointers have no guarantees.
+    let attrs = direct_attrs(&fn_abi.args[1].mode);
+    assert!(!attrs.no_alias);
+    assert!(!attrs.non_null);
+    assert!(!attrs.is_dereferenceable());
+    ControlFlow::Continue(())
+}
+
+fn direct_attrs(mode: &PassMode) -> &ArgAttributes {
+    let PassMode::Direct(attrs) = mode else { panic!("Expected a direct argument: {mode:?}") };
+    attrs
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "arg_attrs_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn read(value: &u8, ptr: *const u8) -> u8 {{
+        if ptr.is_null() {{ *value }} else {{ 0 }}
+    }}
+    "#
+    )?;
+    Ok(())
+}
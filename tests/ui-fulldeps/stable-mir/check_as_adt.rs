@@ -0,0 +1,74 @@
+//@ run-pass
+//! Test that users are able to deconstruct ADT types.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{GenericArgKind, IntTy, Ty, UintTy};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = items.iter().find(|item| item.trimmed_name() == "bytes").unwrap().body();
+    let [vec, int] = body.arg_locals() else { unreachable!() };
+
+    let (def, args) = vec.ty.as_adt().unwrap();
+    assert_eq!(def.trimmed_name(), "Vec");
+    assert!(def.kind().is_struct());
+    let [GenericArgKind::Type(elem), GenericArgKind::Type(alloc)] = &args.0[..] else {
+        panic!("Unexpected generic arguments: {args:?}");
+    };
+    assert_eq!(*elem, Ty::unsigned_ty(UintTy::U8));
+    assert_eq!(alloc.as_adt().unwrap().0.trimmed_name(), "Global");
+
+    assert_eq!(int.ty.as_adt(), None);
+    assert_eq!(Ty::signed_ty(IntTy::I32).as_adt(), None);
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "as_adt_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn bytes(_v: Vec<u8>, _i: i32) {{}}
+    "#
+    )?;
+    Ok(())
+}
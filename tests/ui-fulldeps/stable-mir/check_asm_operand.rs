@@ -0,0 +1,104 @@
+//@ run-pass
+//! Test that users are able to retrieve the registers and directions of inline assembly operands.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+//@ only-x86_64
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{InlineAsmDirection, InlineAsmReg, TerminatorKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let operands = asm_operands(get_item(&items, "copy").unwrap());
+    assert_eq!(operands.len(), 2);
+    assert_eq!(operands[0].direction, Some(InlineAsmDirection::Out { late: false }));
+    assert_eq!(operands[0].reg, Some(InlineAsmReg::RegClass("reg".to_string())));
+    assert!(operands[0].out_place.is_some());
+    assert_eq!(operands[1].direction, Some(InlineAsmDirection::In));
+    assert_eq!(operands[1].reg, Some(InlineAsmReg::RegClass("reg".to_string())));
+    assert!(operands[1].in_value.is_some());
+
+    let operands = asm_operands(get_item(&items, "increment").unwrap());
+    assert_eq!(operands.len(), 2);
+    assert_eq!(operands[0].direction, None);
+    assert_eq!(operands[0].reg, None);
+    assert_eq!(operands[1].direction, Some(InlineAsmDirection::InOut { late: true }));
+    // Explicit registers are reported by their canonical name.
+    assert_eq!(operands[1].reg, Some(InlineAsmReg::Reg("ax".to_string())));
+    ControlFlow::Continue(())
+}
+
+fn asm_operands(item: &CrateItem) -> Vec<stable_mir::mir::InlineAsmOperand> {
+    item.body()
+        .blocks
+        .into_iter()
+        .find_map(|bb| match bb.terminator.kind {
+            TerminatorKind::InlineAsm { operands, .. } => Some(operands),
+            _ => None,
+        })
+        .expect("Expected inline assembly")
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "asm_operand_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    use std::arch::asm;
+
+    pub fn copy(y: u64) -> u64 {{
+        let x: u64;
+        unsafe {{ asm!("mov {{0}}, {{1}}", out(reg) x, in(reg) y) }};
+        x
+    }}
+
+    pub fn increment(mut value: u32) -> u32 {{
+        unsafe {{ asm!("add eax, {{0}}", const 1, inlateout("eax") value) }};
+        value
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,95 @@
+//@ run-pass
+//! Test that users are able to evaluate the associated constants of an impl.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{ImplDef, RigidTy, Ty, UintTy};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let u8_impl = get_impl(Ty::from_rigid_kind(RigidTy::Uint(UintTy::U8)));
+    let n = u8_impl.associated_const("N").unwrap();
+    assert_eq!(n.eval_target_usize(), Ok(4));
+
+    let u16_impl = get_impl(Ty::from_rigid_kind(RigidTy::Uint(UintTy::U16)));
+    assert_eq!(u16_impl.associated_const("N").unwrap().eval_target_usize(), Ok(8));
+
+    // The default value is used if the impl doesn't define the constant.
+    assert_eq!(u8_impl.associated_const("FLAG").unwrap().to_bits(), Ok((1, 1)));
+    assert_eq!(u16_impl.associated_const("FLAG").unwrap().to_bits(), Ok((0, 1)));
+
+    // Only associated constants can be evaluated.
+    assert!(u8_impl.associated_const("Missing").is_err());
+    assert!(u8_impl.associated_const("len").is_err());
+    ControlFlow::Continue(())
+}
+
+fn get_impl(self_ty: Ty) -> ImplDef {
+    stable_mir::all_trait_impls()
+        .into_iter()
+        .find(|def| def.trait_impl().value.self_ty() == self_ty)
+        .unwrap()
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "assoc_const_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub trait MyTrait {{
+        const N: usize;
+        const FLAG: bool = false;
+        fn len(&self) -> usize {{
+            Self::N
+        }}
+    }}
+
+    impl MyTrait for u8 {{
+        const N: usize = 4;
+        const FLAG: bool = true;
+    }}
+
+    impl MyTrait for u16 {{
+        const N: usize = 2 * <u8 as MyTrait>::N;
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,89 @@
+//@ run-pass
+//! Test that users are able to distinguish blanket implementations from concrete ones.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{ImplDef, RigidTy, TyKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let impls = stable_mir::local_crate().trait_impls();
+    assert_eq!(impls.len(), 3);
+
+    let blanket = find_impl(&impls, "Foo");
+    assert!(blanket.is_blanket());
+    assert_eq!(blanket.generics_of().params.len(), 1);
+
+    let concrete = find_impl(&impls, "Bar");
+    assert!(!concrete.is_blanket());
+    let self_ty = concrete.trait_impl().value.self_ty();
+    assert!(matches!(self_ty.kind(), TyKind::RigidTy(RigidTy::Uint(_))));
+    assert!(concrete.generics_of().params.is_empty());
+
+    // Generic implementations are not blanket ones if the self type is not a bare parameter.
+    let generic = find_impl(&impls, "Baz");
+    assert!(!generic.is_blanket());
+    assert_eq!(generic.generics_of().params.len(), 1);
+    ControlFlow::Continue(())
+}
+
+fn find_impl(impls: &[ImplDef], trait_name: &str) -> ImplDef {
+    *impls
+        .iter()
+        .find(|impl_def| impl_def.trait_impl().value.def_id.trimmed_name() == trait_name)
+        .unwrap()
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "blanket_impl_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub trait Foo {{}}
+    pub trait Bar {{}}
+    pub trait Baz {{}}
+
+    impl<T> Foo for T {{}}
+    impl Bar for u8 {{}}
+    impl<T> Baz for Vec<T> {{}}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,147 @@
+//@ run-pass
+//! Test that users are able to check why the body of an item is unavailable.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::TerminatorKind;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+const UPSTREAM_NAME: &str = "upstream";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let caller = get_item(&items, "caller").unwrap();
+    assert_eq!(caller.body_availability(), BodyAvailability::Available);
+
+    let callees = callees(caller);
+    let availability = |path: &str| {
+        let item = callees.iter().find(|item| item.name().ends_with(path)).unwrap();
+        item.body_availability()
+    };
+    assert_eq!(availability("upstream::plain"), BodyAvailability::NotEncoded);
+    assert_eq!(availability("upstream::generic"), BodyAvailability::Available);
+    assert_eq!(availability("upstream::inlined"), BodyAvailability::Available);
+    assert_eq!(availability("external"), BodyAvailability::Foreign);
+    assert_eq!(availability("intrinsics::abort"), BodyAvailability::Intrinsic);
+    ControlFlow::Continue(())
+}
+
+/// Collect the functions called by the given item.
+fn callees(item: &CrateItem) -> Vec<CrateItem> {
+    let body = item.body();
+    body.blocks
+        .iter()
+        .filter_map(|bb| match &bb.terminator.kind {
+            TerminatorKind::Call { func, .. } => {
+                let (def, _) = func.ty(body.locals()).unwrap().kind().fn_def()?;
+                Some(CrateItem(def.def_id()))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// Compile the upstream crate normally, so the input crate can depend on it.
+fn compile_upstream() -> ControlFlow<()> {
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let upstream_path = "body_availability_dep.rs";
+    generate_upstream(&upstream_path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=rlib".to_string(),
+        "--crate-name".to_string(),
+        UPSTREAM_NAME.to_string(),
+        upstream_path.to_string(),
+    ];
+    run!(args, compile_upstream).unwrap();
+
+    let path = "body_availability_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        "--extern".to_string(),
+        format!("{UPSTREAM_NAME}=lib{UPSTREAM_NAME}.rlib"),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_upstream(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #[inline(never)]
+    pub fn plain() -> u32 {{
+        1
+    }}
+
+    pub fn generic<T: Default>() -> T {{
+        T::default()
+    }}
+
+    #[inline]
+    pub fn inlined() -> u32 {{
+        2
+    }}
+    "#
+    )?;
+    Ok(())
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #![feature(core_intrinsics)]
+    #![allow(internal_features)]
+
+    extern "C" {{
+        fn external();
+    }}
+
+    pub fn caller(fail: bool) -> u32 {{
+        if fail {{
+            std::intrinsics::abort();
+        }}
+        unsafe {{ external() }};
+        upstream::plain() + upstream::generic::<u32>() + upstream::inlined()
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,101 @@
+//@ run-pass
+//! Test that users are able to compare two versions of a body.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::diff::{BlockDiff, body_diff};
+use stable_mir::mir::{Statement, StatementKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "sum").unwrap().body();
+
+    // A body has no differences with itself.
+    assert!(body_diff(&body, &body).is_empty());
+
+    // Insert a single statement in the middle of the first block.
+    let mut changed = body.clone();
+    let first = &mut changed.blocks[0];
+    let pos = first.statements.len() / 2;
+    first.statements.insert(pos, Statement { kind: StatementKind::Nop, span: body.span });
+
+    let diff = body_diff(&body, &changed);
+    assert!(diff.added_blocks.is_empty());
+    assert!(diff.removed_blocks.is_empty());
+    assert_eq!(
+        diff.changed_blocks,
+        vec![BlockDiff {
+            block: 0,
+            added_statements: vec![pos],
+            removed_statements: vec![],
+            terminator_changed: false,
+        }]
+    );
+
+    // The inverse diff reports the statement as removed.
+    let diff = body_diff(&changed, &body);
+    assert_eq!(diff.changed_blocks[0].removed_statements, vec![pos]);
+    assert!(diff.changed_blocks[0].added_statements.is_empty());
+
+    // Extra blocks are reported as added.
+    changed.blocks.push(body.blocks[0].clone());
+    let diff = body_diff(&body, &changed);
+    assert_eq!(diff.added_blocks, vec![body.blocks.len()]);
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "body_diff_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn sum(a: u8, b: u8, c: u8) -> u16 {{
+        let ab = a as u16 + b as u16;
+        ab + c as u16
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,112 @@
+//@ run-pass
+//! Test that users are able to edit the statements and terminators of a body.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{
+    Body, MirLocation, Statement, StatementKind, Terminator, TerminatorKind,
+};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let mut body = get_item(&items, "caller").unwrap().body();
+    assert_eq!(body.blocks.len(), 2);
+    let span = body.span;
+    let num_statements = body.blocks[0].statements.len();
+
+    // Insert a `Nop` at the start of the first block.
+    let nop = Statement { kind: StatementKind::Nop, span };
+    let start = MirLocation { block: 0, statement_index: 0 };
+    body.insert_statement(start, nop.clone()).unwrap();
+    assert_eq!(body.blocks[0].statements.len(), num_statements + 1);
+    assert_eq!(body.blocks[0].statements[0], nop);
+
+    // Locations outside the body are rejected.
+    let past_end = MirLocation { block: 0, statement_index: num_statements + 2 };
+    assert!(body.insert_statement(past_end, nop.clone()).is_err());
+    let no_block = MirLocation { block: 2, statement_index: 0 };
+    assert!(body.insert_statement(no_block, nop).is_err());
+
+    // Replace the call by a jump to the return block.
+    let goto = Terminator { kind: TerminatorKind::Goto { target: 1 }, span };
+    let old = body.replace_terminator(0, goto.clone()).unwrap();
+    assert!(matches!(old.kind, TerminatorKind::Call { .. }));
+    assert_eq!(body.blocks[0].terminator, goto);
+
+    // Terminators that jump outside the body are rejected, and the body is left unchanged.
+    let invalid = Terminator { kind: TerminatorKind::Goto { target: 2 }, span };
+    assert!(body.replace_terminator(0, invalid).is_err());
+    assert_eq!(body.blocks[0].terminator, goto);
+
+    validate(&body);
+    ControlFlow::Continue(())
+}
+
+/// Check that every jump of the body targets a block that exists, and that it can be printed.
+fn validate(body: &Body) {
+    for (_, term) in body.terminators() {
+        assert!(term.successors().iter().all(|target| *target < body.blocks.len()));
+    }
+    let mut dump = Vec::new();
+    body.dump(&mut dump, "caller").unwrap();
+    let dump = String::from_utf8(dump).unwrap();
+    assert!(dump.contains("goto -> bb1"), "Unexpected body: {dump}");
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "body_edit_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #[inline(never)]
+    pub fn callee() {{}}
+
+    pub fn caller() {{
+        callee();
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,94 @@
+//@ run-pass
+//! Test that users are able to iterate over all statements and terminators of a body.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{MirLocation, TerminatorKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "two_blocks").unwrap().body();
+    assert_eq!(body.blocks.len(), 2, "Unexpected body: {body:?}");
+
+    let statements: Vec<_> = body.statements().collect();
+    let expected = body.blocks[0].statements.len() + body.blocks[1].statements.len();
+    assert_eq!(statements.len(), expected);
+    assert!(body.blocks.iter().all(|bb| !bb.statements.is_empty()));
+    for (location, stmt) in &statements {
+        assert_eq!(&body.blocks[location.block].statements[location.statement_index], *stmt);
+    }
+    let first_of_second = statements.iter().position(|(location, _)| location.block == 1).unwrap();
+    assert_eq!(first_of_second, body.blocks[0].statements.len());
+
+    let terminators: Vec<_> = body.terminators().collect();
+    assert_eq!(terminators.len(), 2);
+    assert!(matches!(terminators[0].1.kind, TerminatorKind::Call { .. }));
+    assert!(matches!(terminators[1].1.kind, TerminatorKind::Return));
+    assert_eq!(
+        terminators[1].0,
+        MirLocation { block: 1, statement_index: body.blocks[1].statements.len() }
+    );
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "body_statements_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn two_blocks(x: u32) -> u32 {{
+        let y = x ^ 1;
+        let z = helper(y);
+        z ^ 2
+    }}
+
+    #[inline(never)]
+    fn helper(x: u32) -> u32 {{
+        x
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//@ run-pass
+//! Test that users are able to visit the kind of the borrows in a body.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::visit::Location;
+use stable_mir::mir::{BorrowKind, MirVisitor, MutBorrowKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    // The receiver of `push` is borrowed before its argument is evaluated.
+    let kinds = borrow_kinds(get_item(&items, "two_phase").unwrap());
+    assert!(kinds.contains(&BorrowKind::Mut { kind: MutBorrowKind::TwoPhaseBorrow }), "{kinds:?}");
+    assert!(kinds.contains(&BorrowKind::Shared), "{kinds:?}");
+
+    let kinds = borrow_kinds(get_item(&items, "plain").unwrap());
+    assert_eq!(kinds, vec![BorrowKind::Mut { kind: MutBorrowKind::Default }]);
+    ControlFlow::Continue(())
+}
+
+fn borrow_kinds(item: &CrateItem) -> Vec<BorrowKind> {
+    let mut visitor = BorrowVisitor { kinds: vec![] };
+    visitor.visit_body(&item.body());
+    visitor.kinds
+}
+
+struct BorrowVisitor {
+    kinds: Vec<BorrowKind>,
+}
+
+impl MirVisitor for BorrowVisitor {
+    fn visit_borrow_kind(&mut self, kind: &BorrowKind, _location: Location) {
+        self.kinds.push(*kind);
+    }
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "borrow_kind_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn two_phase(mut values: Vec<usize>) -> Vec<usize> {{
+        values.push(values.len());
+        values
+    }}
+
+    pub fn plain(mut value: u32) -> u32 {{
+        let value_ref = &mut value;
+        *value_ref += 1;
+        value
+    }}
+    "#
+    )?;
+    Ok(())
+}
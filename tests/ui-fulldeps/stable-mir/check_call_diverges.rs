@@ -0,0 +1,110 @@
+//@ run-pass
+//! Test that users are able to check whether a function call can return.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{Body, TerminatorKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "caller").unwrap().body();
+    let mut calls = callee_divergence(&body);
+    calls.sort();
+    let expected = [("fail", true), ("helper", false), ("void", true)];
+    assert!(calls.iter().map(|(name, diverges)| (name.as_str(), *diverges)).eq(expected));
+
+    // Other terminators never diverge.
+    let ret = body.blocks.iter().find(|bb| bb.terminator.kind == TerminatorKind::Return).unwrap();
+    assert!(!body.call_diverges(&ret.terminator));
+    ControlFlow::Continue(())
+}
+
+/// Return the name of each function called in the body, and whether the call diverges.
+fn callee_divergence(body: &Body) -> Vec<(String, bool)> {
+    body.blocks
+        .iter()
+        .filter_map(|bb| match &bb.terminator.kind {
+            TerminatorKind::Call { func, .. } => {
+                let (def, _) = func.ty(body.locals()).unwrap().kind().fn_def().unwrap();
+                Some((def.trimmed_name(), body.call_diverges(&bb.terminator)))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "call_diverges_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub enum Void {{}}
+
+    fn fail() -> ! {{
+        loop {{}}
+    }}
+
+    fn helper() -> u8 {{
+        0
+    }}
+
+    fn void() -> Void {{
+        loop {{}}
+    }}
+
+    pub fn caller(x: u8) -> u8 {{
+        if x == 0 {{
+            fail();
+        }}
+        let y = helper();
+        if x == y {{
+            match void() {{}}
+        }}
+        y
+    }}
+    "#
+    )?;
+    Ok(())
+}
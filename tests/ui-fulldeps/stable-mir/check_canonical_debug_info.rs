@@ -0,0 +1,109 @@
+//@ run-pass
+//! Test that users are able to produce a canonical order of the debug information
+//! of a body.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{Place, VarDebugInfo, VarDebugInfoContents};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "shadow").unwrap().body();
+
+    // Build an equivalent body where the debug information is in the opposite order.
+    let mut reversed = body.clone();
+    reversed.var_debug_info.reverse();
+    assert_ne!(body.var_debug_info, reversed.var_debug_info);
+
+    let canonical = body.canonicalize();
+    assert_eq!(canonical.var_debug_info, reversed.canonicalize().var_debug_info);
+    assert_eq!(dump(&canonical), dump(&reversed.canonicalize()));
+
+    // Entries are sorted by name, and then by argument index.
+    let entries: Vec<_> = canonical
+        .var_debug_info
+        .iter()
+        .map(|info| (info.name.as_str(), info.argument_index))
+        .collect();
+    assert_eq!(entries, [("a", None), ("a", Some(2)), ("b", Some(1)), ("c", None)]);
+
+    // Entries that only differ by their value are still sorted deterministically.
+    let mut tied = body.clone();
+    let entry = tied.var_debug_info[0].clone();
+    tied.var_debug_info.extend((0..body.arg_count).map(|local| VarDebugInfo {
+        value: VarDebugInfoContents::Place(Place { local, projection: vec![] }),
+        ..entry.clone()
+    }));
+    let expected = tied.canonicalize();
+    for shift in 1..tied.var_debug_info.len() {
+        let mut permuted = tied.clone();
+        permuted.var_debug_info.rotate_left(shift);
+        assert_eq!(permuted.canonicalize().var_debug_info, expected.var_debug_info);
+        permuted.var_debug_info.reverse();
+        assert_eq!(permuted.canonicalize().var_debug_info, expected.var_debug_info);
+    }
+    ControlFlow::Continue(())
+}
+
+fn dump(body: &mir::Body) -> String {
+    let mut buf = Vec::new();
+    body.dump(&mut buf, "shadow").unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "canonical_debug_info_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn shadow(b: u8, a: u8) -> u8 {{
+        let c = a ^ b;
+        let a = c & 1;
+        a
+    }}
+    "#
+    )?;
+    Ok(())
+}
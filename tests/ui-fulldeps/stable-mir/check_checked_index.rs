@@ -0,0 +1,74 @@
+//@ run-pass
+//! Test that users are able to safely build identifiers from external indices.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::alloc::{AllocId, GlobalAlloc};
+use stable_mir::ty::IndexedVal;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let item = items.iter().find(|item| item.trimmed_name() == "GREETING").unwrap();
+    let def_id = item.def_id();
+    assert_eq!(DefId::from_index_checked(def_id.to_index()), Ok(def_id));
+    assert!(DefId::from_index_checked(usize::MAX).is_err());
+
+    // The static points to the allocation of its string.
+    let static_def = stable_mir::local_crate().statics()[0];
+    let alloc = static_def.eval_initializer().unwrap();
+    let (_, prov) = alloc.provenance.ptrs[0];
+    let alloc_id = AllocId::from_index_checked(prov.0.to_index()).unwrap();
+    assert_eq!(alloc_id, prov.0);
+    assert!(matches!(GlobalAlloc::from(alloc_id), GlobalAlloc::Memory(_)));
+    assert!(AllocId::from_index_checked(usize::MAX).is_err());
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "checked_index_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub static GREETING: &str = "hello";
+    "#
+    )?;
+    Ok(())
+}
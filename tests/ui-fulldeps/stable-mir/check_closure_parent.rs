@@ -0,0 +1,84 @@
+//@ run-pass
+//! Test that users are able to retrieve the item where a closure is defined.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{AggregateKind, Rvalue, StatementKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let outer = get_item(&items, "outer").unwrap();
+    let closures: Vec<_> = outer
+        .body()
+        .blocks
+        .iter()
+        .flat_map(|bb| &bb.statements)
+        .filter_map(|stmt| match &stmt.kind {
+            StatementKind::Assign(_, Rvalue::Aggregate(AggregateKind::Closure(def, _), _)) => {
+                Some(*def)
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(closures.len(), 1, "Expected one closure, but found {closures:?}");
+    let closure = closures[0];
+    assert_eq!(closure.parent(), *outer);
+    assert_eq!(closure.parent().name(), "outer");
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "closure_parent_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn outer(values: &[u8]) -> usize {{
+        let threshold = 10;
+        values.iter().filter(|v| **v > threshold).count()
+    }}
+    "#
+    )?;
+    Ok(())
+}
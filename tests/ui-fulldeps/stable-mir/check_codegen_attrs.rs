@@ -0,0 +1,81 @@
+//@ run-pass
+//! Test that users are able to retrieve the code generation attributes of an item.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let attrs = get_item(&items, "foo").unwrap().codegen_attrs();
+    assert!(attrs.no_mangle);
+    assert!(!attrs.naked);
+    assert_eq!(attrs.export_name, None);
+
+    let attrs = get_item(&items, "bar").unwrap().codegen_attrs();
+    assert!(!attrs.no_mangle);
+    assert_eq!(attrs.export_name.as_deref(), Some("renamed_bar"));
+
+    let attrs = get_item(&items, "baz").unwrap().codegen_attrs();
+    assert_eq!(attrs, CodegenAttrs::default());
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "codegen_attrs_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #[no_mangle]
+    pub extern "C" fn foo() {{}}
+
+    #[export_name = "renamed_bar"]
+    pub extern "C" fn bar() {{}}
+
+    pub fn baz() {{}}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,85 @@
+//@ run-pass
+//! Test that users are able to retrieve the codegen units of a crate.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::{Instance, MonoItem};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let units = stable_mir::codegen_units();
+    assert!(!units.is_empty());
+    assert!(units.iter().all(|unit| !unit.name.is_empty()));
+
+    let main_fn = MonoItem::Fn(Instance::try_from(stable_mir::entry_fn().unwrap()).unwrap());
+    assert!(units.iter().any(|unit| unit.items.contains(&main_fn)));
+
+    // Generic functions are generated for each instantiation.
+    let instances: Vec<_> = units
+        .iter()
+        .flat_map(|unit| &unit.items)
+        .filter_map(|item| match item {
+            MonoItem::Fn(instance) if instance.name().starts_with("id::") => {
+                Some(instance.name())
+            }
+            _ => None,
+        })
+        .collect();
+    assert_eq!(instances.len(), 2, "{instances:?}");
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "codegen_units_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=bin".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    fn id<T>(value: T) -> T {{
+        value
+    }}
+
+    fn main() {{
+        id(1u8);
+        id("one");
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,113 @@
+//@ run-pass
+//! Test that users are able to retrieve the bits of scalar constants.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::visit::{Location, MirVisitor};
+use stable_mir::ty::MirConst;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    assert_eq!(const_bits(&items, "float"), Ok((1.5f32.to_bits() as u128, 4)));
+    assert_eq!(const_bits(&items, "double"), Ok((0x3FF8_0000_0000_0000, 8)));
+    assert_eq!(const_bits(&items, "negative"), Ok((0xFE, 1)));
+    assert_eq!(const_bits(&items, "boolean"), Ok((1, 1)));
+    assert_eq!(const_bits(&items, "character"), Ok(('z' as u128, 4)));
+    assert!(const_bits(&items, "string").is_err());
+    ControlFlow::Continue(())
+}
+
+/// Get the bits of the single constant used in the given function.
+fn const_bits(items: &CrateItems, name: &str) -> Result<(u128, usize), Error> {
+    let body = get_item(items, name).unwrap().body();
+    let mut visitor = ConstVisitor { consts: vec![] };
+    visitor.visit_body(&body);
+    assert_eq!(visitor.consts.len(), 1, "Unexpected constants in `{name}`: {:?}", visitor.consts);
+    visitor.consts[0].to_bits()
+}
+
+struct ConstVisitor {
+    consts: Vec<MirConst>,
+}
+
+impl MirVisitor for ConstVisitor {
+    fn visit_mir_const(&mut self, constant: &MirConst, location: Location) {
+        self.consts.push(constant.clone());
+        self.super_mir_const(constant, location);
+    }
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "const_bits_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn float() -> f32 {{
+        1.5
+    }}
+
+    pub fn double() -> f64 {{
+        1.5
+    }}
+
+    pub fn negative() -> i8 {{
+        -2
+    }}
+
+    pub fn boolean() -> bool {{
+        true
+    }}
+
+    pub fn character() -> char {{
+        'z'
+    }}
+
+    pub fn string() -> &'static str {{
+        "z"
+    }}
+    "#
+    )?;
+    Ok(())
+}
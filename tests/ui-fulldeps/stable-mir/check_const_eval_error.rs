@@ -0,0 +1,95 @@
+//@ run-pass
+//! Test that errors from constant evaluation point to the constant that failed.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::Instance;
+use stable_mir::ty::{ConstDef, GenericArgs, Reveal, UnevaluatedConst};
+use stable_mir::*;
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let item = *items
+        .iter()
+        .find(|item| item.kind() == ItemKind::Const && item.name().ends_with("::X"))
+        .expect("Expected constant `Holder::X`");
+    let def = ConstDef::try_from(item).unwrap();
+    let cnst = UnevaluatedConst { def, args: GenericArgs(vec![]), promoted: None };
+    let error = cnst.eval(&GenericArgs(vec![]), Reveal::All).unwrap_err();
+
+    // The compiler has already reported the division by zero, so the error points to the constant.
+    let msg = error.to_string();
+    assert!(msg.contains("`Holder::X`"), "Unexpected error: {msg}");
+    assert!(msg.contains("The compiler reported an error"), "Unexpected error: {msg}");
+    let span = error.span().expect("Expected the location of the constant").get_lines();
+    let item_span = item.span().get_lines();
+    assert_eq!(span.start_line, item_span.start_line);
+    assert!(!msg.contains(&item.span().get_filename()), "Unexpected source path: {msg}");
+
+    // The error from an instance evaluation should also carry a location.
+    let instance = Instance::try_from(item).unwrap();
+    let error = instance.try_const_eval(instance.ty()).unwrap_err();
+    assert!(error.to_string().contains("`Holder::X`"), "Unexpected error: {error}");
+    assert_eq!(error.span().unwrap().get_lines().start_line, item_span.start_line);
+
+    // Stop the compilation, since the compiler has already reported the evaluation error.
+    ControlFlow::Break(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "const_eval_error_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    let result = run!(args, test_stable_mir);
+    assert_matches!(result, Err(CompilerError::Failed | CompilerError::Interrupted(())));
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    // Free constants are evaluated during analysis, which would abort the compilation before
+    // the test runs. Associated constants are only evaluated when requested.
+    write!(
+        file,
+        r#"
+    #![allow(unconditional_panic)]
+
+    pub struct Holder;
+
+    impl Holder {{
+        pub const X: u8 = 1 / 0;
+    }}
+    "#
+    )?;
+    Ok(())
+}
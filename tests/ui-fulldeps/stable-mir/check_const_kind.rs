@@ -0,0 +1,102 @@
+//@ run-pass
+//! Test that users are able to inspect the kind of MIR constants.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::visit::{Location, MirVisitor};
+use stable_mir::ty::{ConstantKind, MirConst};
+use stable_mir::*;
+use std::assert_matches::assert_matches;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let literal = single_const(&items, "literal");
+    assert_matches!(literal.kind(), ConstantKind::Allocated(alloc) if alloc.read_uint() == Ok(7));
+
+    let param = single_const(&items, "param");
+    let ConstantKind::Param(param_const) = param.kind() else {
+        panic!("Expected a const parameter, but found {:?}", param.kind())
+    };
+    assert_eq!(param_const.name, "N");
+    assert_eq!(param_const.index, 0);
+    ControlFlow::Continue(())
+}
+
+/// Get the single constant used in the given function.
+fn single_const(items: &CrateItems, name: &str) -> MirConst {
+    let body = get_item(items, name).unwrap().body();
+    let mut visitor = ConstVisitor { consts: vec![] };
+    visitor.visit_body(&body);
+    assert_eq!(visitor.consts.len(), 1, "Unexpected constants in `{name}`: {:?}", visitor.consts);
+    visitor.consts.pop().unwrap()
+}
+
+struct ConstVisitor {
+    consts: Vec<MirConst>,
+}
+
+impl MirVisitor for ConstVisitor {
+    fn visit_mir_const(&mut self, constant: &MirConst, location: Location) {
+        self.consts.push(constant.clone());
+        self.super_mir_const(constant, location);
+    }
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "const_kind_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn literal() -> u64 {{
+        7
+    }}
+
+    pub fn param<const N: usize>() -> usize {{
+        N
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,118 @@
+//@ run-pass
+//! Test that users are able to retrieve the layout of coroutine bodies.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{Body, CoroutineLayout};
+use stable_mir::ty::{RigidTy, TyKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    // The `async fn` itself only builds the coroutine.
+    let wrapper = get_item(&items, "wait_twice").unwrap().body();
+    assert!(wrapper.coroutine_layout().is_none());
+    assert!(get_item(&items, "ready").unwrap().body().coroutine_layout().is_none());
+
+    let coroutines: Vec<Body> = items
+        .iter()
+        .map(|item| item.body())
+        .filter(|body| body.coroutine_layout().is_some())
+        .collect();
+    assert_eq!(coroutines.len(), 2, "Expected one coroutine per `async fn`");
+
+    for body in &coroutines {
+        let layout = body.coroutine_layout().unwrap();
+        // The coroutine itself, followed by the resume argument.
+        assert_eq!(body.arg_locals().len(), 2);
+        assert_eq!(layout.resume_local, Some(2));
+        let resume_ty = body.local_decl(2).unwrap().ty;
+        assert!(
+            matches!(resume_ty.kind(), TyKind::RigidTy(RigidTy::Ref(..))),
+            "Unexpected resume type: {resume_ty:?}"
+        );
+        assert_eq!(layout.variant_fields.len(), layout.variant_spans.len());
+        for fields in &layout.variant_fields {
+            assert!(fields.iter().all(|local| *local < layout.field_tys.len()));
+        }
+    }
+
+    // A layout can only be attached to a body that takes its resume argument.
+    let layout = coroutines[0].coroutine_layout().unwrap().clone();
+    assert!(wrapper.clone().with_coroutine_layout(layout.clone()).is_err());
+    let invalid = CoroutineLayout { resume_local: Some(0), ..layout.clone() };
+    assert!(coroutines[0].clone().with_coroutine_layout(invalid).is_err());
+    let body = coroutines[0].clone().with_coroutine_layout(layout.clone()).unwrap();
+    assert_eq!(body.coroutine_layout(), Some(&layout));
+
+    // One suspension point per `.await`.
+    let suspensions: Vec<usize> = coroutines
+        .iter()
+        .map(|body| body.coroutine_layout().unwrap().suspension_variants().count())
+        .collect();
+    assert!(suspensions.contains(&0), "Unexpected suspension points: {suspensions:?}");
+    assert!(suspensions.contains(&2), "Unexpected suspension points: {suspensions:?}");
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "coroutine_layout_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        "--edition=2021".to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub async fn ready(x: u32) -> u32 {{
+        x + 1
+    }}
+
+    pub async fn wait_twice(x: u32) -> u32 {{
+        let a = ready(x).await;
+        let b = ready(a).await;
+        a + b
+    }}
+    "#
+    )?;
+    Ok(())
+}
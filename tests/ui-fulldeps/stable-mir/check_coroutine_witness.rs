@@ -0,0 +1,117 @@
+//@ run-pass
+//! Test that users are able to retrieve the types held across suspension points.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{GenericArgKind, RigidTy, Ty, TyKind};
+use stable_mir::visitor::{Visitable, Visitor};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "hold").unwrap().body();
+    let coroutine_ty = body.ret_local().ty;
+    let TyKind::RigidTy(RigidTy::Coroutine(_, args, _)) = coroutine_ty.kind() else {
+        panic!("Expected a coroutine, but found {coroutine_ty:?}")
+    };
+
+    // The witness is one of the generic arguments of the coroutine.
+    let (def, witness_args) = args
+        .0
+        .iter()
+        .find_map(|arg| match arg {
+            GenericArgKind::Type(ty) => match ty.kind() {
+                TyKind::RigidTy(RigidTy::CoroutineWitness(def, args)) => Some((def, args)),
+                _ => None,
+            },
+            _ => None,
+        })
+        .expect("Expected a coroutine witness");
+    let witness_tys = def.witness_tys(&witness_args);
+    assert!(witness_tys.iter().any(is_string), "Unexpected types: {witness_tys:?}");
+
+    // The type visitor only reaches the types held by the coroutine if it opts in.
+    assert!(coroutine_ty.visit(&mut StringFinder { expand: false }).is_continue());
+    assert!(coroutine_ty.visit(&mut StringFinder { expand: true }).is_break());
+    ControlFlow::Continue(())
+}
+
+fn is_string(ty: &Ty) -> bool {
+    matches!(ty.kind(), TyKind::RigidTy(RigidTy::Adt(def, _)) if def.trimmed_name() == "String")
+}
+
+/// Stops as soon as a `String` is found.
+struct StringFinder {
+    expand: bool,
+}
+
+impl Visitor for StringFinder {
+    type Break = ();
+
+    fn visit_ty(&mut self, ty: &Ty) -> ControlFlow<Self::Break> {
+        if is_string(ty) { ControlFlow::Break(()) } else { ty.super_visit(self) }
+    }
+
+    fn expand_coroutine_witness(&self) -> bool {
+        self.expand
+    }
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "coroutine_witness_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        "--edition=2021".to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub async fn hold(len: usize) -> usize {{
+        let owned = String::with_capacity(len);
+        ready().await;
+        owned.capacity()
+    }}
+
+    async fn ready() {{}}
+    "#
+    )?;
+    Ok(())
+}
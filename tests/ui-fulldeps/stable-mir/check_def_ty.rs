@@ -19,7 +19,7 @@
 extern crate stable_mir;
 
 use rustc_smir::rustc_internal;
-use stable_mir::ty::{Ty, ForeignItemKind};
+use stable_mir::ty::{Ty, ForeignItemKind, UintTy};
 use stable_mir::*;
 use std::io::Write;
 use std::ops::ControlFlow;
@@ -35,6 +35,11 @@ fn test_def_tys() -> ControlFlow<()> {
         match item.name().as_str() {
             "STATIC_STR" => assert!(ty.kind().is_ref()),
             "CONST_U32" => assert!(ty.kind().is_integral()),
+            "TUPLE" => check_tuple(ty),
+            "UNIT" => {
+                assert!(ty.is_unit());
+                assert_eq!(ty.tuple_fields(), Some(vec![]));
+            }
             "main" => { check_fn_def(ty) }
             _ => unreachable!("Unexpected item: `{item:?}`")
         }
@@ -64,6 +69,14 @@ fn test_def_tys() -> ControlFlow<()> {
     ControlFlow::Continue(())
 }
 
+fn check_tuple(ty: Ty) {
+    assert!(!ty.is_unit());
+    let fields = ty.tuple_fields().expect(&format!("Expected tuple type, but found: {ty}"));
+    let expected = [UintTy::U8, UintTy::U16, UintTy::U32].map(Ty::unsigned_ty);
+    assert_eq!(fields, expected);
+    assert_eq!(Ty::unsigned_ty(UintTy::U8).tuple_fields(), None);
+}
+
 fn check_fn_def(ty: Ty) {
     let kind = ty.kind();
     let (def, args) = kind.fn_def().expect(&format!("Expected function type, but found: {ty}"));
@@ -97,6 +110,8 @@ fn generate_input(path: &str) -> std::io::Result<()> {
         #![feature(core_intrinsics)]
         static STATIC_STR: &str = "foo";
         const CONST_U32: u32 = 0u32;
+        static TUPLE: (u8, u16, u32) = (1, 2, 3);
+        static UNIT: () = ();
 
         fn main() {{
             let _c = core::char::from_u32(99);
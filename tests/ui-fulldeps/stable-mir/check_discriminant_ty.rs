@@ -0,0 +1,111 @@
+//@ run-pass
+//! Test that users are able to retrieve the discriminant type of a type.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{Rvalue, StatementKind};
+use stable_mir::ty::{IntTy, Ty, UintTy};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    // The type of the discriminant read matches the discriminant type of the enum.
+    let body = get_item(&items, "is_a").unwrap().body();
+    let (place, rvalue) = body
+        .blocks
+        .iter()
+        .flat_map(|bb| &bb.statements)
+        .find_map(|stmt| match &stmt.kind {
+            StatementKind::Assign(_, rvalue @ Rvalue::Discriminant(place)) => Some((place, rvalue)),
+            _ => None,
+        })
+        .expect("Expected a discriminant read");
+    let enum_ty = place.ty(body.locals()).unwrap();
+    let discr_ty = enum_ty.discriminant_ty().unwrap();
+    assert!(discr_ty.kind().is_integral());
+    assert_eq!(discr_ty, Ty::signed_ty(IntTy::Isize));
+    assert_eq!(rvalue.ty(body.locals()).unwrap(), discr_ty);
+
+    // The representation of the enum determines its discriminant type.
+    let body = get_item(&items, "repr_u8").unwrap().body();
+    assert_eq!(body.arg_locals()[0].ty.discriminant_ty(), Some(Ty::unsigned_ty(UintTy::U8)));
+
+    // The discriminant type of a generic type is unknown.
+    let body = get_item(&items, "generic").unwrap().body();
+    assert_eq!(body.arg_locals()[0].ty.discriminant_ty(), None);
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "discriminant_ty_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub enum E {{
+        A,
+        B,
+    }}
+
+    #[repr(u8)]
+    pub enum Small {{
+        A = 1,
+        B = 2,
+    }}
+
+    pub fn is_a(e: &E) -> bool {{
+        matches!(e, E::A)
+    }}
+
+    pub fn repr_u8(s: Small) -> Small {{
+        s
+    }}
+
+    pub fn generic<T>(t: T) -> T {{
+        t
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//@ run-pass
+//! Test that users are able to retrieve the discriminants of an enum.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{IndexedVal, VariantIdx};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "take").unwrap().body();
+    let [explicit, negative, generic, strukt] = body.arg_locals() else { unreachable!() };
+
+    assert_eq!(explicit.ty.discriminants(), Some(vec![(variant(0), 1), (variant(1), 5)]));
+    assert_eq!(negative.ty.discriminants(), Some(vec![(variant(0), 0xff), (variant(1), 0)]));
+    assert_eq!(generic.ty.discriminants(), Some(vec![(variant(0), 0), (variant(1), 1)]));
+    assert_eq!(strukt.ty.discriminants(), None);
+    ControlFlow::Continue(())
+}
+
+fn variant(idx: usize) -> VariantIdx {
+    VariantIdx::to_val(idx)
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "discriminants_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub enum Explicit {{
+        A = 1,
+        B = 5,
+    }}
+
+    #[repr(i8)]
+    pub enum Negative {{
+        Minus = -1,
+        Zero,
+    }}
+
+    pub struct Plain;
+
+    pub fn take(_e: Explicit, _n: Negative, _g: Option<u8>, _p: Plain) {{}}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,109 @@
+//@ run-pass
+//! Test that users are able to export the control-flow graph of a body to the DOT format.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let diamond = get_item(&items, "diamond").unwrap().body();
+    let dot = diamond.to_dot("diamond");
+    assert!(dot.starts_with("digraph \"diamond\" {"), "Unexpected header: {dot}");
+    assert_eq!(nodes(&dot), diamond.blocks.len(), "Unexpected nodes: {dot}");
+    let num_successors: usize =
+        diamond.blocks.iter().map(|bb| bb.terminator.successors().len()).sum();
+    assert_eq!(edges(&dot).len(), num_successors, "Unexpected edges: {dot}");
+    // The switch splits into two branches that join again before returning.
+    assert_eq!(diamond.blocks.len(), 4, "Expected a diamond: {dot}");
+    assert_eq!(num_successors, 4, "Expected a diamond: {dot}");
+    assert!(!dot.contains("style=dashed"));
+
+    let cleanup = get_item(&items, "cleanup").unwrap().body();
+    let dot = cleanup.to_dot("cleanup");
+    assert_eq!(nodes(&dot), cleanup.blocks.len(), "Unexpected nodes: {dot}");
+    let dashed: Vec<_> =
+        edges(&dot).into_iter().filter(|edge| edge.contains("style=dashed")).collect();
+    assert!(!dashed.is_empty(), "Expected an unwind edge: {dot}");
+    assert!(dashed.iter().all(|edge| edge.contains("label=\"unwind\"")), "{dashed:?}");
+    ControlFlow::Continue(())
+}
+
+/// Count the node declarations in the DOT output.
+fn nodes(dot: &str) -> usize {
+    dot.lines()
+        .filter(|line| {
+            let mut tokens = line.split_whitespace();
+            tokens.next().is_some_and(|token| token.starts_with("bb"))
+                && tokens.next().is_some_and(|token| token.starts_with("[label="))
+        })
+        .count()
+}
+
+/// Collect the edges in the DOT output.
+fn edges(dot: &str) -> Vec<&str> {
+    dot.lines().filter(|line| line.split_whitespace().nth(1) == Some("->")).collect()
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "dot_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn diamond(cond: bool) -> u8 {{
+        if cond {{ 1 }} else {{ 2 }}
+    }}
+
+    pub fn cleanup(v: Vec<u8>) -> usize {{
+        may_panic();
+        v.len()
+    }}
+
+    #[inline(never)]
+    fn may_panic() {{}}
+    "#
+    )?;
+    Ok(())
+}
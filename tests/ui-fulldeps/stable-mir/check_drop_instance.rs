@@ -0,0 +1,116 @@
+//@ run-pass
+//! Test that users are able to resolve the drop glue of a `Drop` terminator.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::Instance;
+use stable_mir::mir::{Body, Terminator, TerminatorKind};
+use stable_mir::ty::{GenericArgKind, RigidTy, Ty, TyKind, UintTy};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    // Find the instance `consume::<Vec<u8>>` called by `caller`.
+    let caller = get_item(&items, "caller").unwrap().body();
+    let callee = caller
+        .blocks
+        .iter()
+        .find_map(|bb| match &bb.terminator.kind {
+            TerminatorKind::Call { func, .. } => {
+                let TyKind::RigidTy(RigidTy::FnDef(def, args)) =
+                    func.ty(caller.locals()).unwrap().kind()
+                else {
+                    return None;
+                };
+                (def.trimmed_name() == "consume").then(|| Instance::resolve(def, &args).unwrap())
+            }
+            _ => None,
+        })
+        .expect("Expected a call to `consume`");
+    let mono = callee.body().unwrap();
+    let arg_ty = mono.arg_locals()[0].ty;
+    let TyKind::RigidTy(RigidTy::Adt(vec_def, args)) = arg_ty.kind() else {
+        panic!("Expected a vector argument, but found {arg_ty:?}")
+    };
+    assert_eq!(vec_def.trimmed_name(), "Vec");
+    assert_eq!(args.0[0], GenericArgKind::Type(Ty::unsigned_ty(UintTy::U8)));
+    let expected = Instance::resolve_drop_in_place(arg_ty);
+
+    // The dropped `T` of the generic body is instantiated with the arguments of the instance.
+    let generic = get_item(&items, "consume").unwrap().body();
+    assert_eq!(generic.drop_instance(get_drop(&generic), &callee), Some(expected));
+
+    // The monomorphized body already drops the concrete type.
+    assert_eq!(mono.drop_instance(get_drop(&mono), &callee), Some(expected));
+
+    // Other terminators have nothing to drop.
+    let ret = Terminator { kind: TerminatorKind::Return, span: get_drop(&mono).span };
+    assert_eq!(mono.drop_instance(&ret, &callee), None);
+    ControlFlow::Continue(())
+}
+
+fn get_drop(body: &Body) -> &Terminator {
+    body.blocks
+        .iter()
+        .map(|bb| &bb.terminator)
+        .find(|term| matches!(term.kind, TerminatorKind::Drop { .. }))
+        .expect("Expected a drop")
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "drop_instance_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #[inline(never)]
+    pub fn consume<T>(_value: T) {{}}
+
+    pub fn caller(value: Vec<u8>) {{
+        consume(value);
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,73 @@
+//@ run-pass
+//@ check-run-results
+//! Test that users are able to emit diagnostics at a given span.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let target = items.iter().find(|item| item.trimmed_name() == "target").unwrap();
+    stable_mir::emit_diagnostic(target.span(), DiagLevel::Warning, "custom warning from a tool");
+    ControlFlow::Continue(())
+}
+
+/// This function emits an error, which should make the compilation fail.
+fn test_error() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let target = items.iter().find(|item| item.trimmed_name() == "target").unwrap();
+    stable_mir::emit_diagnostic(target.span(), DiagLevel::Error, "custom error from a tool");
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+///
+/// The diagnostics printed by both compilations are checked against the expected output.
+fn main() {
+    let path = "emit_diagnostic_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+    assert_eq!(run!(args, test_error), Err(CompilerError::Failed));
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn target() {{}}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,75 @@
+//@ run-pass
+//! Test that users are able to retrieve the instance where the program starts.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::{Instance, InstanceKind};
+use stable_mir::ty::{RigidTy, TyKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let main_fn = Instance::try_from(stable_mir::entry_fn().unwrap()).unwrap();
+    let entry = stable_mir::entry_instance().unwrap();
+    assert_ne!(entry, main_fn);
+    assert_eq!(entry.kind, InstanceKind::Item);
+    assert_eq!(entry.name(), "std::rt::lang_start::<std::process::ExitCode>");
+
+    // The entry is instantiated with the return type of `main`.
+    let ret_ty = main_fn.fn_abi().unwrap().ret.ty;
+    let TyKind::RigidTy(RigidTy::FnDef(_, args)) = entry.ty().kind() else { unreachable!() };
+    assert_eq!(args.0[0].expect_ty(), &ret_ty);
+    assert!(entry.body().is_some());
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "entry_instance_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=bin".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    use std::process::ExitCode;
+
+    fn main() -> ExitCode {{
+        ExitCode::SUCCESS
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,110 @@
+//@ run-pass
+//! Test that users are able to evaluate unevaluated constants in a concrete context.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::visit::{Location, MirVisitor};
+use stable_mir::ty::{
+    ConstantKind, GenericArgKind, GenericArgs, MirConst, Reveal, Ty, UintTy, UnevaluatedConst,
+};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "max_of").unwrap().body();
+    let mut visitor = UnevaluatedVisitor { consts: vec![] };
+    visitor.visit_body(&body);
+    assert_eq!(visitor.consts.len(), 1, "Unexpected constants: {:?}", visitor.consts);
+    let unevaluated = &visitor.consts[0];
+
+    // Evaluate `<T as Bounded>::MAX` for `T = u8`.
+    let args = GenericArgs(vec![GenericArgKind::Type(Ty::unsigned_ty(UintTy::U8))]);
+    let value = unevaluated.eval(&args, Reveal::All).unwrap();
+    assert_eq!(value.ty(), Ty::unsigned_ty(UintTy::U8));
+    assert_eq!(value.to_bits(), Ok((255, 1)));
+
+    // The same constant has a different value for `T = u16`.
+    let args = GenericArgs(vec![GenericArgKind::Type(Ty::unsigned_ty(UintTy::U16))]);
+    let value = unevaluated.eval(&args, Reveal::All).unwrap();
+    assert_eq!(value.to_bits(), Ok((1000, 2)));
+    ControlFlow::Continue(())
+}
+
+struct UnevaluatedVisitor {
+    consts: Vec<UnevaluatedConst>,
+}
+
+impl MirVisitor for UnevaluatedVisitor {
+    fn visit_mir_const(&mut self, constant: &MirConst, location: Location) {
+        if let ConstantKind::Unevaluated(unevaluated) = constant.kind() {
+            self.consts.push(unevaluated.clone());
+        }
+        self.super_mir_const(constant, location);
+    }
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "eval_unevaluated_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub trait Bounded: Sized {{
+        const MAX: Self;
+    }}
+
+    impl Bounded for u8 {{
+        const MAX: u8 = 255;
+    }}
+
+    impl Bounded for u16 {{
+        const MAX: u16 = 1000;
+    }}
+
+    pub fn max_of<T: Bounded>() -> T {{
+        T::MAX
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,118 @@
+//@ run-pass
+//! Test that users are able to retrieve the entry and exit blocks of a body.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{BasicBlock, Body, Terminator, TerminatorKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let body = get_item(&items, "two_returns").unwrap().body();
+    assert_eq!(body.entry_block(), 0);
+    check_exit_blocks(&body);
+    let returns = body.return_blocks();
+    assert!(!returns.is_empty());
+
+    // Emulate a body with multiple return paths by appending another return block.
+    let mut blocks = body.blocks.clone();
+    let span = blocks[0].terminator.span;
+    blocks.push(BasicBlock {
+        statements: vec![],
+        terminator: Terminator { kind: TerminatorKind::Return, span },
+    });
+    let new_return = blocks.len() - 1;
+    let multi_return = Body::new(
+        blocks,
+        body.locals().to_vec(),
+        body.arg_locals().len(),
+        body.var_debug_info.clone(),
+        body.spread_arg(),
+        body.span,
+    );
+    let mut expected = returns.clone();
+    expected.push(new_return);
+    assert_eq!(multi_return.return_blocks(), expected);
+    assert_eq!(multi_return.resume_blocks(), body.resume_blocks());
+
+    let body = get_item(&items, "with_cleanup").unwrap().body();
+    check_exit_blocks(&body);
+    assert!(!body.resume_blocks().is_empty(), "Expected a resume block");
+    ControlFlow::Continue(())
+}
+
+/// Check that the exit blocks are exactly the ones with a `Return` or `Resume` terminator.
+fn check_exit_blocks(body: &Body) {
+    for (idx, bb) in body.blocks.iter().enumerate() {
+        let is_return = matches!(bb.terminator.kind, TerminatorKind::Return);
+        let is_resume = matches!(bb.terminator.kind, TerminatorKind::Resume);
+        assert_eq!(body.return_blocks().contains(&idx), is_return);
+        assert_eq!(body.resume_blocks().contains(&idx), is_resume);
+    }
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "exit_blocks_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn two_returns(cond: bool) -> u8 {{
+        if cond {{
+            return 1;
+        }}
+        2
+    }}
+
+    pub fn with_cleanup(v: Vec<u8>) -> usize {{
+        may_panic();
+        v.len()
+    }}
+
+    #[inline(never)]
+    fn may_panic() {{}}
+    "#
+    )?;
+    Ok(())
+}
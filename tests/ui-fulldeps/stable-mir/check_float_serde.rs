@@ -0,0 +1,96 @@
+//@ run-pass
+//! Test that users are able to serialize float constants without losing precision.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate serde_json;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{Operand, Rvalue, StatementKind};
+use stable_mir::ty::MirConst;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "tenth").unwrap().body();
+    let constant = body.blocks[0]
+        .statements
+        .iter()
+        .find_map(|stmt| match &stmt.kind {
+            StatementKind::Assign(_, Rvalue::Use(Operand::Constant(constant))) => {
+                Some(constant.const_.clone())
+            }
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(constant.to_bits().unwrap(), (0.1f64.to_bits() as u128, 8));
+
+    // Float constants are serialized as the bytes of their value, so the exact bit pattern can
+    // be recovered from the serialized output.
+    let value = f64::from_ne_bytes(serialized_bytes(&constant).try_into().unwrap());
+    assert_eq!(value.to_bits(), 0.1f64.to_bits());
+    ControlFlow::Continue(())
+}
+
+/// Extract the bytes of an evaluated constant from its JSON representation.
+fn serialized_bytes(constant: &MirConst) -> Vec<u8> {
+    let json = serde_json::to_value(constant).unwrap();
+    json["kind"]["Allocated"]["bytes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|byte| byte.as_u64().unwrap().try_into().unwrap())
+        .collect()
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "float_serde_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn tenth() -> f64 {{
+        0.1
+    }}
+    "#
+    )?;
+    Ok(())
+}
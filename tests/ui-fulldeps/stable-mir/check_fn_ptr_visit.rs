@@ -0,0 +1,105 @@
+//@ run-pass
+//! Test that users are able to visit the input and output types of function
+//! pointers.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::visit::Location;
+use stable_mir::mir::MirVisitor;
+use stable_mir::ty::{RigidTy, Ty, TyKind, UintTy};
+use stable_mir::visitor::{Visitable, Visitor};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = items.iter().find(|item| item.trimmed_name() == "call").unwrap().body();
+    let mut visitor = FnPtrVisitor { fn_ptrs: 0, types: vec![] };
+    visitor.visit_body(&body);
+
+    // Both the argument and the local store a function pointer.
+    assert!(visitor.fn_ptrs >= 2, "Unexpected number of function pointers: {}", visitor.fn_ptrs);
+    assert!(visitor.types.contains(&Ty::unsigned_ty(UintTy::U8)));
+    assert!(visitor.types.contains(&Ty::unsigned_ty(UintTy::U16)));
+    ControlFlow::Continue(())
+}
+
+/// Walk every type of the body, and collect the types found inside function pointer types.
+struct FnPtrVisitor {
+    fn_ptrs: usize,
+    types: Vec<Ty>,
+}
+
+impl MirVisitor for FnPtrVisitor {
+    fn visit_ty(&mut self, ty: &Ty, _location: Location) {
+        if let TyKind::RigidTy(RigidTy::FnPtr(sig)) = ty.kind() {
+            self.fn_ptrs += 1;
+            let mut collector = TyCollector { types: vec![] };
+            let _ = sig.visit(&mut collector);
+            self.types.extend(collector.types);
+        }
+    }
+}
+
+struct TyCollector {
+    types: Vec<Ty>,
+}
+
+impl Visitor for TyCollector {
+    type Break = ();
+
+    fn visit_ty(&mut self, ty: &Ty) -> ControlFlow<Self::Break> {
+        self.types.push(*ty);
+        ty.super_visit(self)
+    }
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "fn_ptr_visit_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn call(f: fn(u8) -> u16, x: u8) -> u16 {{
+        let g: fn(u8) -> u16 = f;
+        g(x)
+    }}
+    "#
+    )?;
+    Ok(())
+}
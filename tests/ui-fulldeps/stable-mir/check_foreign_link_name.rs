@@ -0,0 +1,95 @@
+//@ run-pass
+//! Test that users are able to retrieve the link name of foreign items.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{ForeignDef, ForeignItemKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let modules = stable_mir::local_crate().foreign_modules();
+    assert_eq!(modules.len(), 1);
+    let items = modules[0].module().items();
+
+    let foo = get_foreign(&items, "foo");
+    assert_eq!(CrateItem(foo.def_id()).foreign_link_name().as_deref(), Some("real"));
+    assert!(!is_variadic(foo));
+
+    let printf = get_foreign(&items, "printf");
+    assert_eq!(CrateItem(printf.def_id()).foreign_link_name().as_deref(), Some("printf"));
+    assert!(is_variadic(printf));
+
+    // Items that are not foreign have no link name.
+    let local_items = stable_mir::all_local_items();
+    let caller = local_items.iter().find(|item| item.trimmed_name() == "caller").unwrap();
+    assert_eq!(caller.foreign_link_name(), None);
+    ControlFlow::Continue(())
+}
+
+fn get_foreign<'a>(items: &'a [ForeignDef], name: &str) -> &'a ForeignDef {
+    items.iter().find(|item| item.trimmed_name() == name).unwrap()
+}
+
+fn is_variadic(item: &ForeignDef) -> bool {
+    let ForeignItemKind::Fn(def) = item.kind() else { panic!("Expected a function") };
+    def.fn_sig().value.c_variadic
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "foreign_link_name_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    extern "C" {{
+        #[link_name = "real"]
+        fn foo();
+        fn printf(format: *const u8, ...) -> i32;
+    }}
+
+    pub fn caller() {{
+        unsafe {{
+            foo();
+            printf(b"%d\0".as_ptr(), 1);
+        }}
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,86 @@
+//@ run-pass
+//! Test that users are able to retrieve the layout of `gen` coroutines, which have no resume
+//! argument.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::Body;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let coroutines: Vec<Body> = items
+        .iter()
+        .map(|item| item.body())
+        .filter(|body| body.coroutine_layout().is_some())
+        .collect();
+    assert_eq!(coroutines.len(), 1, "Expected one coroutine for the `gen` block");
+
+    let body = &coroutines[0];
+    let layout = body.coroutine_layout().unwrap();
+    // Only the coroutine itself is an argument.
+    assert_eq!(body.arg_locals().len(), 1);
+    assert_eq!(layout.resume_local, None);
+    // One suspension point per `yield`.
+    assert_eq!(layout.suspension_variants().count(), 2);
+
+    let copy = body.clone().with_coroutine_layout(layout.clone()).unwrap();
+    assert_eq!(copy.coroutine_layout(), Some(layout));
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "gen_coroutine_layout_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        "--edition=2024".to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #![feature(gen_blocks)]
+
+    pub fn numbers(x: u32) -> impl Iterator<Item = u32> {{
+        gen move {{
+            yield x;
+            yield x + 1;
+        }}
+    }}
+    "#
+    )?;
+    Ok(())
+}
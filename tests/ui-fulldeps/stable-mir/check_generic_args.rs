@@ -0,0 +1,97 @@
+//@ run-pass
+//! Test that users are able to retrieve the generic arguments of a type.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::TerminatorKind;
+use stable_mir::ty::{GenericArgKind, Ty, UintTy};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let take = get_item(&items, "take").unwrap();
+    let body = take.body();
+    let [map, tuple] = body.arg_locals() else { unreachable!() };
+
+    // `HashMap` has a third argument for the hasher, which defaults to `RandomState`.
+    let args = map.ty.generic_args().unwrap();
+    let [GenericArgKind::Type(key), GenericArgKind::Type(value), GenericArgKind::Type(hasher)] =
+        args.0.as_slice()
+    else {
+        panic!("Unexpected arguments: {args:?}")
+    };
+    assert_eq!(*key, Ty::unsigned_ty(UintTy::U8));
+    assert_eq!(*value, Ty::unsigned_ty(UintTy::U16));
+    assert_eq!(hasher.as_adt().unwrap().0.trimmed_name(), "RandomState");
+
+    // Function definitions also carry their generic arguments.
+    let TerminatorKind::Call { func, .. } = &body.blocks[0].terminator.kind else {
+        panic!("Expected a call to `identity`")
+    };
+    let fn_def_ty = func.ty(body.locals()).unwrap();
+    let u32_ty = Ty::unsigned_ty(UintTy::U32);
+    assert_eq!(fn_def_ty.generic_args().unwrap().0, [GenericArgKind::Type(u32_ty)]);
+
+    assert_eq!(tuple.ty.generic_args(), None);
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "generic_args_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    use std::collections::HashMap;
+
+    pub fn identity<T>(value: T) -> T {{
+        value
+    }}
+
+    pub fn take(_map: HashMap<u8, u16>, _tuple: (u8, u16)) -> u32 {{
+        identity(5)
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//@ run-pass
+//! Test that users are able to extract the content of global allocations.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::alloc::GlobalAlloc;
+use stable_mir::mir::mono::StaticDef;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let memory = single_alloc(&items, "MEMORY");
+    let alloc = memory.as_memory().unwrap();
+    assert_eq!(alloc.read_uint(), Ok(42));
+    assert_eq!(memory.as_static(), None);
+    assert_eq!(memory.as_function(), None);
+
+    let other = single_alloc(&items, "REF_OTHER");
+    assert_eq!(other.as_static().unwrap().name(), "OTHER");
+    assert_eq!(other.as_memory(), None);
+
+    let function = single_alloc(&items, "FUNCTION");
+    assert_eq!(function.as_function().unwrap().name(), "answer");
+    assert_eq!(function.as_memory(), None);
+    ControlFlow::Continue(())
+}
+
+/// Get the only allocation that is reachable from the given static.
+fn single_alloc(items: &CrateItems, name: &str) -> GlobalAlloc {
+    let item = items.iter().find(|crate_item| crate_item.name() == name).unwrap();
+    let mut allocs = StaticDef::try_from(item).unwrap().reachable_allocs().unwrap();
+    assert_eq!(allocs.len(), 1, "Unexpected allocations: {allocs:?}");
+    allocs.pop().unwrap().1
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "global_alloc_accessors_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn answer() -> u8 {{
+        42
+    }}
+
+    pub static OTHER: u8 = 0;
+    pub static MEMORY: &u8 = &42;
+    pub static REF_OTHER: &u8 = &OTHER;
+    pub static FUNCTION: fn() -> u8 = answer;
+    "#
+    )?;
+    Ok(())
+}
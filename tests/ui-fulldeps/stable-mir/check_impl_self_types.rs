@@ -0,0 +1,88 @@
+//@ run-pass
+//! Test that users are able to retrieve the implementations of a trait with their self types.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{RigidTy, TyKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let shape = stable_mir::local_crate()
+        .trait_decls()
+        .into_iter()
+        .find(|trait_def| trait_def.trimmed_name() == "Shape")
+        .unwrap();
+    let impls = shape.impls_with_self_types();
+    assert_eq!(impls.len(), 2);
+
+    let mut self_types: Vec<String> = impls
+        .iter()
+        .map(|(self_ty, impl_def)| {
+            assert_eq!(impl_def.trait_impl().value.self_ty(), *self_ty);
+            let TyKind::RigidTy(RigidTy::Adt(def, _)) = self_ty.kind() else {
+                panic!("Expected an ADT, but found {self_ty:?}")
+            };
+            def.trimmed_name()
+        })
+        .collect();
+    self_types.sort();
+    assert_eq!(self_types, ["Circle", "Square"]);
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "impl_self_types_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub trait Shape {{}}
+
+    pub struct Circle;
+    pub struct Square;
+
+    impl Shape for Circle {{}}
+    impl Shape for Square {{}}
+
+    #[derive(Clone)]
+    pub struct Other;
+    "#
+    )?;
+    Ok(())
+}
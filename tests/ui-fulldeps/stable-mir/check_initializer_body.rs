@@ -0,0 +1,109 @@
+//@ run-pass
+//! Test that users are able to retrieve the body of constant and static
+//! initializers.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::StaticDef;
+use stable_mir::mir::{Body, TerminatorKind};
+use stable_mir::ty::{ConstDef, ForeignItemKind, RigidTy, TyKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let value = StaticDef::try_from(get_item(&items, "VALUE")).unwrap();
+    let body = value.initializer_body().unwrap();
+    assert_eq!(called_fns(&body), ["compute"]);
+
+    let offset = ConstDef::try_from(get_item(&items, "OFFSET")).unwrap();
+    let body = offset.initializer_body().unwrap();
+    assert_eq!(called_fns(&body), ["compute"]);
+
+    // Foreign statics don't have an initializer.
+    let module = local_crate().foreign_modules()[0].module();
+    let ForeignItemKind::Static(external) = module.items()[0].kind() else {
+        panic!("Expected a foreign static")
+    };
+    assert!(external.initializer_body().is_none());
+    ControlFlow::Continue(())
+}
+
+/// The names of the functions called by the given body.
+fn called_fns(body: &Body) -> Vec<String> {
+    body.blocks
+        .iter()
+        .filter_map(|block| match &block.terminator.kind {
+            TerminatorKind::Call { func, .. } => {
+                let TyKind::RigidTy(RigidTy::FnDef(def, _)) = func.ty(body.locals()).unwrap().kind()
+                else {
+                    panic!("Expected a function definition, but found {func:?}")
+                };
+                Some(def.trimmed_name())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn get_item(items: &CrateItems, name: &str) -> CrateItem {
+    *items.iter().find(|crate_item| crate_item.trimmed_name() == name).unwrap()
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "initializer_body_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub const fn compute() -> u32 {{
+        6 * 7
+    }}
+
+    pub static VALUE: u32 = compute();
+
+    pub const OFFSET: u32 = compute() + 1;
+
+    extern "C" {{
+        pub static EXTERNAL: u32;
+    }}
+    "#
+    )?;
+    Ok(())
+}
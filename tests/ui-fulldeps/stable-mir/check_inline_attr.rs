@@ -0,0 +1,80 @@
+//@ run-pass
+//! Test that users are able to retrieve the inlining hint of functions.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let inline_attr = |name: &str| get_item(&items, name).unwrap().inline_attr();
+    assert_eq!(inline_attr("always"), InlineAttr::Always);
+    assert_eq!(inline_attr("hint"), InlineAttr::Hint);
+    assert_eq!(inline_attr("never"), InlineAttr::Never);
+    assert_eq!(inline_attr("plain"), InlineAttr::None);
+    assert_eq!(inline_attr("CONST"), InlineAttr::None);
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "inline_attr_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #[inline(always)]
+    pub fn always() {{}}
+
+    #[inline]
+    pub fn hint() {{}}
+
+    #[inline(never)]
+    pub fn never() {{}}
+
+    pub fn plain() {{}}
+
+    pub const CONST: u8 = 1;
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,87 @@
+//@ run-pass
+//! Test that users are able to retrieve the inlined calls that contain a scope.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = items.iter().find(|item| item.trimmed_name() == "caller").unwrap().body();
+    let scope_of = |name: &str| {
+        let info = body.var_debug_info.iter().find(|info| info.name == name).unwrap();
+        info.source_info.scope
+    };
+
+    // The argument of the caller is not inlined.
+    assert!(body.inlined_at(scope_of("y")).is_empty());
+
+    // The argument of `add_one` is inlined, nested in the call to `double`.
+    let calls = body.inlined_at(scope_of("x"));
+    let names: Vec<_> = calls.iter().map(|(instance, _)| instance.trimmed_name()).collect();
+    assert_eq!(names, ["add_one", "double"]);
+    assert!(body.source_scopes().iter().any(|scope| scope.inlined.is_some()));
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "inlined_at_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        "-Copt-level=3".to_string(),
+        "-Zinline-mir".to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #[inline(always)]
+    fn add_one(x: u32) -> u32 {{
+        x.wrapping_add(1)
+    }}
+
+    #[inline(always)]
+    fn double(z: u32) -> u32 {{
+        add_one(z).wrapping_mul(2)
+    }}
+
+    pub fn caller(y: u32) -> u32 {{
+        double(y)
+    }}
+    "#
+    )?;
+    Ok(())
+}
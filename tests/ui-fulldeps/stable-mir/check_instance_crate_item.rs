@@ -0,0 +1,88 @@
+//@ run-pass
+//! Test that users are able to retrieve the item of an instance.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::{Instance, InstanceKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    // A plain function maps back to its item.
+    let plain = *get_item(&items, "plain").unwrap();
+    let instance = Instance::try_from(plain).unwrap();
+    let item = instance.crate_item().unwrap();
+    assert_eq!(item, plain);
+    assert_eq!(item.span(), plain.span());
+
+    // Drop glue is a shim, which is not backed by an item.
+    let take = get_item(&items, "take").unwrap();
+    let droppy = take.body().arg_locals()[0].ty;
+    let drop_glue = Instance::resolve_drop_in_place(droppy);
+    assert_eq!(drop_glue.kind, InstanceKind::Shim);
+    assert_eq!(drop_glue.crate_item(), None);
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "instance_crate_item_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn plain() -> u8 {{
+        1
+    }}
+
+    pub struct Droppy;
+
+    impl Drop for Droppy {{
+        fn drop(&mut self) {{}}
+    }}
+
+    pub fn take(_droppy: Droppy) {{}}
+    "#
+    )?;
+    Ok(())
+}
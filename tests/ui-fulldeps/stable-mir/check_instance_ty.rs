@@ -0,0 +1,92 @@
+//@ run-pass
+//! Test that users are able to retrieve the monomorphized type of an instance.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::TerminatorKind;
+use stable_mir::mir::mono::Instance;
+use stable_mir::ty::{GenericArgKind, RigidTy, Ty, TyKind, UintTy};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = items.iter().find(|item| item.trimmed_name() == "caller").unwrap().body();
+    let instance = body
+        .blocks
+        .iter()
+        .find_map(|bb| match &bb.terminator.kind {
+            TerminatorKind::Call { func, .. } => {
+                let kind = func.ty(body.locals()).unwrap().kind();
+                let (def, args) = kind.fn_def()?;
+                Some(Instance::resolve(def, args).unwrap())
+            }
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(instance.trimmed_name(), "id::<u8>");
+
+    let TyKind::RigidTy(RigidTy::FnDef(def, args)) = instance.ty().kind() else {
+        panic!("Expected a function definition, but found {:?}", instance.ty());
+    };
+    assert_eq!(def.trimmed_name(), "id");
+    assert_eq!(args.0, [GenericArgKind::Type(Ty::unsigned_ty(UintTy::U8))]);
+
+    // The signature of the instance type has no generic parameters left.
+    let sig = instance.ty().kind().fn_sig().unwrap().skip_binder();
+    assert_eq!(sig.output(), Ty::unsigned_ty(UintTy::U8));
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "instance_ty_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    fn id<T>(val: T) -> T {{
+        val
+    }}
+
+    pub fn caller(val: u8) -> u8 {{
+        id(val)
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,78 @@
+//@ run-pass
+//! Test that users are able to retrieve the range of values of integer types.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::target::{MachineInfo, MachineSize};
+use stable_mir::ty::{IntTy, UintTy};
+use std::io::Write;
+use std::ops::ControlFlow;
+
+/// This function checks the integer ranges of the target.
+fn test_int_range() -> ControlFlow<()> {
+    let machine = MachineInfo::target();
+    assert_eq!(IntTy::I8.max_value(&machine), 127);
+    assert_eq!(IntTy::I8.min_value(&machine), -128);
+    assert_eq!(IntTy::I32.max_value(&machine), i32::MAX as i128);
+    assert_eq!(IntTy::I32.min_value(&machine), i32::MIN as i128);
+    assert_eq!(IntTy::I128.max_value(&machine), i128::MAX);
+    assert_eq!(IntTy::I128.min_value(&machine), i128::MIN);
+
+    assert_eq!(UintTy::U8.max_value(&machine), 255);
+    assert_eq!(UintTy::U8.min_value(&machine), 0);
+    assert_eq!(UintTy::U64.max_value(&machine), u64::MAX as u128);
+    assert_eq!(UintTy::U128.max_value(&machine), u128::MAX);
+
+    // The pointer sized types depend on the target.
+    let width = machine.pointer_width;
+    assert_eq!(Some(UintTy::Usize.max_value(&machine)), width.unsigned_int_max());
+    assert_eq!(IntTy::Isize.max_value(&machine), (UintTy::Usize.max_value(&machine) >> 1) as i128);
+    assert_eq!(IntTy::Isize.min_value(&machine), -IntTy::Isize.max_value(&machine) - 1);
+
+    // The range only depends on the given machine.
+    let machine_32 = MachineInfo { pointer_width: MachineSize::from_bits(32), ..machine };
+    assert_eq!(UintTy::Usize.max_value(&machine_32), u32::MAX as u128);
+    assert_eq!(IntTy::Isize.max_value(&machine_32), i32::MAX as i128);
+    assert_eq!(IntTy::Isize.min_value(&machine_32), i32::MIN as i128);
+    let machine_64 = MachineInfo { pointer_width: MachineSize::from_bits(64), ..machine };
+    assert_eq!(UintTy::Usize.max_value(&machine_64), u64::MAX as u128);
+    assert_eq!(IntTy::Isize.min_value(&machine_64), i64::MIN as i128);
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "int_range_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec!["rustc".to_string(), "--crate-type=lib".to_string(), path.to_string()];
+    run!(args, test_int_range).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn dummy() {{}}
+    "#
+    )?;
+    Ok(())
+}
@@ -61,6 +61,7 @@ fn test_intrinsics() -> ControlFlow<()> {
 /// `rustc_intrinsic_must_be_overridden`.
 fn check_instance(instance: &Instance) {
     assert_eq!(instance.kind, InstanceKind::Intrinsic);
+    assert!(!instance.is_drop_glue());
     let name = instance.intrinsic_name().unwrap();
     if instance.has_body() {
         let Some(body) = instance.body() else { unreachable!("Expected a body") };
@@ -0,0 +1,75 @@
+//@ run-pass
+//! Test that users are able to retrieve the definition ID of a crate item.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::IndexedVal;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let item = items.iter().find(|item| item.trimmed_name() == "answer").unwrap();
+
+    let def_id = item.def_id();
+    assert_eq!(def_id, item.0);
+    assert_eq!(def_id.name(), item.name());
+    assert_eq!(def_id.trimmed_name(), "answer");
+    assert_eq!(def_id.name(), "inner::answer");
+
+    // The ID can be used to recreate the item.
+    let same_item = CrateItem(DefId::from_index_checked(def_id.to_index()).unwrap());
+    assert_eq!(&same_item, item);
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "item_def_id_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub mod inner {{
+        pub fn answer() -> u8 {{
+            42
+        }}
+    }}
+    "#
+    )?;
+    Ok(())
+}
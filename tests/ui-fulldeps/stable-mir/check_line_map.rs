@@ -0,0 +1,95 @@
+//@ run-pass
+//! Test that users are able to map the statements of a body to source lines.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::MirLocation;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let body = get_item(&items, "straight_line").unwrap().body();
+    let line_map = body.line_map();
+    let num_locations: usize = body.blocks.iter().map(|bb| bb.statements.len() + 1).sum();
+    assert_eq!(line_map.len(), num_locations);
+    assert_eq!(line_map[0].0, MirLocation { block: 0, statement_index: 0 });
+
+    // The body only has one block, and its statements follow the source order.
+    assert_eq!(body.blocks.len(), 1);
+    let lines: Vec<usize> = line_map.iter().map(|(_, line)| *line).collect();
+    assert!(lines.windows(2).all(|pair| pair[0] <= pair[1]), "Unexpected lines: {lines:?}");
+    for line in 3..=5 {
+        assert!(lines.contains(&line), "Expected line {line} in {lines:?}");
+    }
+
+    // Code expanded from `assert_eq!` is mapped to the line of the macro call.
+    let body = get_item(&items, "with_macro").unwrap().body();
+    let lines: Vec<usize> = body.line_map().iter().map(|(_, line)| *line).collect();
+    assert!(lines.iter().all(|line| (9..=13).contains(line)), "Unexpected lines: {lines:?}");
+    assert!(lines.contains(&11), "Expected the macro line in {lines:?}");
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "line_map_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn straight_line(a: u32, b: u32) -> u32 {{
+        let x = a ^ b;
+        let y = x & a;
+        let z = y | b;
+        z
+    }}
+
+    pub fn with_macro(a: u32) -> u32 {{
+        let v = a >> 1;
+        assert_eq!(v, a / 2, "unexpected value");
+        v
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,89 @@
+//@ run-pass
+//! Test that users are able to classify the locals of a body.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{Body, Local, LocalKind};
+use stable_mir::ty::{RigidTy, TyKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "classify").unwrap().body();
+
+    assert_eq!(body.local_kind(0), LocalKind::ReturnPointer);
+    assert_eq!(body.local_kind(1), LocalKind::Arg);
+    assert_eq!(body.local_kind(2), LocalKind::Arg);
+    assert_eq!(body.local_kind(find_local(&body, "sum")), LocalKind::Var);
+
+    // The overflow check of the multiplication is stored in a temporary tuple.
+    let (temp, _) = body
+        .local_decls()
+        .find(|(_, decl)| matches!(decl.ty.kind(), TyKind::RigidTy(RigidTy::Tuple(_))))
+        .expect("Expected a temporary for the checked multiplication");
+    assert_eq!(body.local_kind(temp), LocalKind::Temp);
+    ControlFlow::Continue(())
+}
+
+fn find_local(body: &Body, name: &str) -> Local {
+    body.var_debug_info
+        .iter()
+        .find(|info| info.name == name)
+        .and_then(|info| info.local())
+        .unwrap_or_else(|| panic!("Expected local `{name}`"))
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "local_kind_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn classify(a: u32, b: u32) -> u32 {{
+        let sum = a.wrapping_add(b);
+        sum * 2
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//@ run-pass
+//! Test that users are able to retrieve the types of the locals of a monomorphized body.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::TerminatorKind;
+use stable_mir::mir::mono::Instance;
+use stable_mir::ty::{RigidTy, Ty, TyKind, UintTy};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let caller = get_item(&items, "caller").unwrap().body();
+    let TerminatorKind::Call { func, .. } = &caller.blocks[0].terminator.kind else {
+        unreachable!("Expected a call to `pair`")
+    };
+    let TyKind::RigidTy(RigidTy::FnDef(def, args)) = func.ty(caller.locals()).unwrap().kind()
+    else {
+        unreachable!()
+    };
+    let body = Instance::resolve(def, &args).unwrap().body().unwrap();
+
+    let u8_ty = Ty::unsigned_ty(UintTy::U8);
+    let types = body.local_types();
+    assert_eq!(types.len(), body.locals().len());
+    assert_eq!(types[0], Ty::new_tuple(&[u8_ty, u8_ty]));
+    assert_eq!(types[1], u8_ty);
+    assert!(types.iter().zip(body.locals()).all(|(ty, decl)| *ty == decl.ty));
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "local_types_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn pair<T: Copy>(value: T) -> (T, T) {{
+        (value, value)
+    }}
+
+    pub fn caller() -> (u8, u8) {{
+        pair(1u8)
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,98 @@
+//@ run-pass
+//! Test that users are able to retrieve how locals are used in a body.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{Body, Local};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "read_twice").unwrap().body();
+    let usages = body.local_usages();
+    assert_eq!(usages.len(), body.locals().len());
+
+    // `y` is assigned once, and read twice.
+    let y = usages[find_local(&body, "y")].clone();
+    assert!(y.is_mutated());
+    assert_eq!(y.mutating.len(), 1, "Unexpected usages: {y:?}");
+    assert_eq!(y.non_mutating.len(), 2, "Unexpected usages: {y:?}");
+    // At least the debug information and the storage markers.
+    assert!(!y.non_use.is_empty());
+
+    // The argument is never modified, but it is read to compute `y`.
+    let x = &usages[find_local(&body, "x")];
+    assert!(!x.is_mutated());
+    assert!(!x.non_mutating.is_empty());
+
+    // The return value is written, and then read by the `Return` terminator.
+    let ret = &usages[0];
+    assert!(ret.is_mutated());
+    assert!(!ret.non_mutating.is_empty());
+    ControlFlow::Continue(())
+}
+
+fn find_local(body: &Body, name: &str) -> Local {
+    body.var_debug_info
+        .iter()
+        .find(|info| info.name == name)
+        .and_then(|info| info.local())
+        .unwrap_or_else(|| panic!("Expected local `{name}`"))
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "local_usages_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn read_twice(x: u8) -> u8 {{
+        let y = x / 2;
+        let a = y & 1;
+        let b = y | 2;
+        a ^ b
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,96 @@
+//@ run-pass
+//! Test that users are able to retrieve the line and column of a location in a
+//! MIR visitor.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::visit::Location;
+use stable_mir::mir::{MirVisitor, Statement};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "combine").unwrap().body();
+    let mut visitor = LineCollector { lines: vec![] };
+    visitor.visit_body(&body);
+
+    // The function body spans lines 3 to 6 of the input.
+    let lines = visitor.lines;
+    assert!(lines.is_sorted(), "Unexpected lines: {lines:?}");
+    assert!(lines.iter().all(|line| (3..=6).contains(line)), "Unexpected lines: {lines:?}");
+    for line in 3..=5 {
+        assert!(lines.contains(&line), "Missing line {line}: {lines:?}");
+    }
+    ControlFlow::Continue(())
+}
+
+/// Collect the starting line of each statement.
+struct LineCollector {
+    lines: Vec<usize>,
+}
+
+impl MirVisitor for LineCollector {
+    fn visit_statement(&mut self, stmt: &Statement, location: Location) {
+        let (line, col) = location.line_col().unwrap();
+        assert_eq!(line, stmt.span.get_lines().start_line);
+        assert!(col > 0);
+        self.lines.push(line);
+        self.super_statement(stmt, location)
+    }
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "location_line_col_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn combine(a: u8, b: u8) -> u8 {{
+        let x = a / 2;
+        let y = b & 3;
+        x | y
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,93 @@
+//@ run-pass
+//! Test that users are able to retrieve the macro expansions that produced a span.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::TerminatorKind;
+use stable_mir::ty::MacroKind;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "greet").unwrap().body();
+
+    // The call to the print function is generated by `println!`.
+    let backtraces: Vec<_> = body
+        .blocks
+        .iter()
+        .filter(|bb| matches!(bb.terminator.kind, TerminatorKind::Call { .. }))
+        .map(|bb| bb.terminator.span.macro_backtrace())
+        .collect();
+    let println = backtraces
+        .iter()
+        .flatten()
+        .find(|expn| expn.macro_name == "println")
+        .unwrap_or_else(|| panic!("Expected a `println` expansion: {backtraces:?}"));
+    assert_eq!(println.kind, MacroKind::Bang);
+    assert_eq!(println.call_site.get_lines().start_line, 4);
+    assert!(println.call_site.get_filename().ends_with("macro_backtrace_input.rs"));
+    assert!(!println.def_site.get_filename().ends_with("macro_backtrace_input.rs"));
+    // The outermost expansion is the macro that was called in the source.
+    assert!(backtraces.iter().any(|backtrace| backtrace.last() == Some(println)));
+
+    // Spans that don't come from macros have no expansion.
+    let first_stmt = &body.blocks[0].statements[0];
+    assert_eq!(first_stmt.span.get_lines().start_line, 3);
+    assert!(first_stmt.span.macro_backtrace().is_empty());
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "macro_backtrace_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn greet(name: &str, count: usize) {{
+        let total = count.wrapping_mul(2);
+        println!("Hello, {{name}}: {{total}}");
+    }}
+    "#
+    )?;
+    Ok(())
+}
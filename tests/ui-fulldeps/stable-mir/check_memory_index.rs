@@ -0,0 +1,102 @@
+//@ run-pass
+//! Test that users are able to retrieve the memory order of the fields of a type.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::abi::FieldsShape;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    // Fields of `repr(C)` types are placed in source order.
+    let (offsets, memory_index) = fields_of(&items, "c_layout");
+    assert_eq!(memory_index, vec![0, 1, 2]);
+    assert_eq!(offsets, vec![0, 8, 16]);
+
+    // The compiler reorders the fields of `repr(Rust)` types to reduce padding.
+    let (offsets, memory_index) = fields_of(&items, "rust_layout");
+    assert_ne!(memory_index, vec![0, 1, 2]);
+    let mut by_memory_order: Vec<_> = (0..offsets.len()).collect();
+    by_memory_order.sort_by_key(|field| memory_index[*field]);
+    assert!(by_memory_order.windows(2).all(|w| offsets[w[0]] < offsets[w[1]]));
+    ControlFlow::Continue(())
+}
+
+/// Get the offset in bytes and the memory index of each field of the type returned by the
+/// function with the given name.
+fn fields_of(items: &CrateItems, name: &str) -> (Vec<usize>, Vec<usize>) {
+    let item = items.iter().find(|item| item.trimmed_name() == name).unwrap();
+    let shape = item.body().ret_local().ty.layout().unwrap().shape();
+    let FieldsShape::Arbitrary { offsets, memory_index } = shape.fields else {
+        panic!("Expected arbitrary fields, but found {:?}", shape.fields)
+    };
+    (offsets.iter().map(|offset| offset.bytes()).collect(), memory_index)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "memory_index_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub struct RustLayout {{
+        pub a: u8,
+        pub b: u64,
+        pub c: u8,
+    }}
+
+    #[repr(C)]
+    pub struct CLayout {{
+        pub a: u8,
+        pub b: u64,
+        pub c: u8,
+    }}
+
+    pub fn rust_layout() -> RustLayout {{
+        RustLayout {{ a: 1, b: 2, c: 3 }}
+    }}
+
+    pub fn c_layout() -> CLayout {{
+        CLayout {{ a: 1, b: 2, c: 3 }}
+    }}
+    "#
+    )?;
+    Ok(())
+}
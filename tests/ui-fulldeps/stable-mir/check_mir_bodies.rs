@@ -0,0 +1,85 @@
+//@ run-pass
+//! Test that users are able to retrieve multiple bodies at once.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    assert!(items.len() >= 3, "Unexpected items: {items:?}");
+
+    let bodies = stable_mir::mir_bodies(&items);
+    assert_eq!(bodies.len(), items.len());
+    for (item, batch_body) in items.iter().zip(&bodies) {
+        let body = item.body();
+        assert_eq!(format!("{batch_body:?}"), format!("{body:?}"), "Mismatch for {item:?}");
+    }
+
+    assert!(stable_mir::mir_bodies(&[]).is_empty());
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "mir_bodies_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn add(a: u32, b: u32) -> u32 {{
+        a.wrapping_add(b)
+    }}
+
+    pub fn first(v: &[u8]) -> Option<u8> {{
+        v.first().copied()
+    }}
+
+    pub static VALUE: u64 = 10;
+
+    pub struct Wrapper(pub u8);
+
+    impl Wrapper {{
+        pub fn get(&self) -> u8 {{
+            self.0
+        }}
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,107 @@
+//@ run-pass
+//! Test that users are able to visit the bodies of all monomorphized instances.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::MonoItem;
+use stable_mir::mir::{BasicBlock, Body, MirVisitor};
+use stable_mir::*;
+use std::collections::HashSet;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let mut counter = BlockCounter::default();
+    visit_all_mono_bodies(&mut counter);
+
+    // Compute the expected numbers from the unique instances of every codegen unit.
+    let instances: HashSet<_> = codegen_units()
+        .into_iter()
+        .flat_map(|unit| unit.items)
+        .filter_map(|item| if let MonoItem::Fn(instance) = item { Some(instance) } else { None })
+        .collect();
+    let bodies: Vec<_> = instances.iter().filter_map(|instance| instance.body()).collect();
+    assert_eq!(counter.bodies, bodies.len());
+    assert_eq!(counter.blocks, bodies.iter().map(|body| body.blocks.len()).sum::<usize>());
+
+    // `id` is generated twice, once for each type.
+    let names: Vec<_> = instances.iter().map(|instance| instance.trimmed_name()).collect();
+    assert!(names.contains(&"id::<u8>".to_string()));
+    assert!(names.contains(&"id::<u16>".to_string()));
+    assert!(counter.bodies >= 4);
+    ControlFlow::Continue(())
+}
+
+#[derive(Default)]
+struct BlockCounter {
+    bodies: usize,
+    blocks: usize,
+}
+
+impl MirVisitor for BlockCounter {
+    fn visit_body(&mut self, body: &Body) {
+        self.bodies += 1;
+        self.super_body(body)
+    }
+
+    fn visit_basic_block(&mut self, bb: &BasicBlock) {
+        self.blocks += 1;
+        self.super_basic_block(bb)
+    }
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "mono_bodies_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    fn id<T>(val: T) -> T {{
+        val
+    }}
+
+    pub fn pick(first: bool, a: u8, b: u8) -> u8 {{
+        if first {{ id(a) }} else {{ id(b) }}
+    }}
+
+    pub fn wide(val: u16) -> u16 {{
+        id(val)
+    }}
+    "#
+    )?;
+    Ok(())
+}
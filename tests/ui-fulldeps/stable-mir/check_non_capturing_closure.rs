@@ -0,0 +1,75 @@
+//@ run-pass
+//! Test that users are able to check whether a closure captures any variable.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{RigidTy, TyKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = items.iter().find(|item| item.trimmed_name() == "closures").unwrap().body();
+    let mut closures = body.local_decls().filter_map(|(_, decl)| match decl.ty.kind() {
+        TyKind::RigidTy(RigidTy::Closure(def, args)) => Some((def, args)),
+        _ => None,
+    });
+
+    let (add, add_args) = closures.next().unwrap();
+    assert!(!add.is_non_capturing(&add_args));
+
+    let (inc, inc_args) = closures.next().unwrap();
+    assert!(inc.is_non_capturing(&inc_args));
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "non_capturing_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn closures(x: u8) -> u8 {{
+        let add = |y: u8| x + y;
+        let inc = |y: u8| y + 1;
+        add(1) + inc(2)
+    }}
+    "#
+    )?;
+    Ok(())
+}
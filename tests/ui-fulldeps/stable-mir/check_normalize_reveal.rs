@@ -0,0 +1,115 @@
+//@ run-pass
+//! Test that users are able to normalize types and evaluate constants with and without
+//! revealing opaque types.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::visit::{Location, MirVisitor};
+use stable_mir::ty::{
+    AliasKind, ConstantKind, GenericArgKind, GenericArgs, MirConst, Reveal, Ty, TyKind, UintTy,
+    UnevaluatedConst,
+};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let item = items.iter().find(|item| item.trimmed_name() == "make").unwrap();
+    let output = item.ty().kind().fn_sig().unwrap().skip_binder().output();
+    assert!(matches!(output.kind(), TyKind::Alias(AliasKind::Opaque, _)));
+
+    let user_facing = output.normalize(Reveal::UserFacing).unwrap();
+    assert!(matches!(user_facing.kind(), TyKind::Alias(AliasKind::Opaque, _)));
+
+    let revealed = output.normalize(Reveal::All).unwrap();
+    assert_eq!(revealed, Ty::unsigned_ty(UintTy::U8));
+
+    // Evaluate `<T as Size>::SIZE` for `T = impl Size`.
+    let body = items.iter().find(|item| item.trimmed_name() == "size_of").unwrap().body();
+    let mut visitor = UnevaluatedVisitor { consts: vec![] };
+    visitor.visit_body(&body);
+    assert_eq!(visitor.consts.len(), 1, "Unexpected constants: {:?}", visitor.consts);
+    let args = GenericArgs(vec![GenericArgKind::Type(output)]);
+
+    // The constant cannot be resolved without knowing the hidden type.
+    let error = visitor.consts[0].eval(&args, Reveal::UserFacing).unwrap_err();
+    assert!(error.to_string().contains("too generic"), "Unexpected error: {error}");
+
+    let value = visitor.consts[0].eval(&args, Reveal::All).unwrap();
+    assert_eq!(value.to_bits(), Ok((1, 1)));
+    ControlFlow::Continue(())
+}
+
+struct UnevaluatedVisitor {
+    consts: Vec<UnevaluatedConst>,
+}
+
+impl MirVisitor for UnevaluatedVisitor {
+    fn visit_mir_const(&mut self, constant: &MirConst, location: Location) {
+        if let ConstantKind::Unevaluated(unevaluated) = constant.kind() {
+            self.consts.push(unevaluated.clone());
+        }
+        self.super_mir_const(constant, location);
+    }
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "normalize_reveal_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub trait Size {{
+        const SIZE: u8;
+    }}
+
+    impl Size for u8 {{
+        const SIZE: u8 = 1;
+    }}
+
+    pub fn make() -> impl Size {{
+        1u8
+    }}
+
+    pub fn size_of<T: Size>(_: T) -> u8 {{
+        T::SIZE
+    }}
+    "#
+    )?;
+    Ok(())
+}
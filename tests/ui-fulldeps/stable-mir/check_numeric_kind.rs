@@ -0,0 +1,83 @@
+//@ run-pass
+//! Test that users are able to classify numeric types into a flat family.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::target::MachineInfo;
+use stable_mir::ty::NumericKind;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = items.iter().find(|item| item.trimmed_name() == "numbers").unwrap().body();
+    let kinds: Vec<_> = body.arg_locals().iter().map(|local| local.ty.numeric_kind()).collect();
+    assert_eq!(
+        kinds,
+        [
+            Some(NumericKind::I32),
+            Some(NumericKind::U8),
+            Some(NumericKind::F64),
+            Some(NumericKind::Usize),
+            None
+        ]
+    );
+
+    let machine = MachineInfo::target();
+    assert_eq!(NumericKind::I32.bit_width(&machine), 32);
+    assert_eq!(NumericKind::U8.bit_width(&machine), 8);
+    assert_eq!(NumericKind::F64.bit_width(&machine), 64);
+    assert_eq!(NumericKind::Usize.bit_width(&machine), machine.pointer_width.bits());
+
+    assert!(NumericKind::I32.is_signed_int());
+    assert!(!NumericKind::U8.is_signed_int());
+    assert!(NumericKind::F64.is_float());
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "numeric_kind_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn numbers(_a: i32, _b: u8, _c: f64, _d: usize, _e: bool) {{}}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,84 @@
+//@ run-pass
+//! Test that users are able to serialize opaque values containing special characters.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate serde_json;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use std::fmt;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// A value whose debug representation contains characters that must be escaped in JSON.
+struct RawText(&'static str);
+
+impl fmt::Debug for RawText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let text = "a \"quoted\" value\nwith a new line, a \\ backslash and a \t tab";
+    let opaque = stable_mir::opaque(&RawText(text));
+    assert_eq!(opaque.to_string(), text);
+
+    let json = serde_json::to_string(&opaque).unwrap();
+    assert!(!json.contains('\n'));
+    let parsed: String = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, text);
+
+    // Opaque values produced by the compiler are escaped the same way.
+    for item in stable_mir::all_local_items() {
+        let json = serde_json::to_string(&item.body()).unwrap();
+        let _: serde_json::Value = serde_json::from_str(&json).unwrap();
+    }
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "opaque_serde_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn greeting() -> &'static str {{
+        "say \"hi\"\n"
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,84 @@
+//@ run-pass
+//! Test that users are able to check whether overflow checks and debug assertions
+//! are enabled.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{AssertMessage, TerminatorKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// Read the flags of the current compilation, and check that the body of `add` only has an
+/// overflow check if overflow checks are enabled.
+fn read_flags() -> ControlFlow<(), (bool, bool)> {
+    let overflow_checks = stable_mir::overflow_checks_enabled();
+    let items = stable_mir::all_local_items();
+    let add = items.iter().find(|item| item.trimmed_name() == "add").unwrap();
+    let has_overflow_assert = add.body().blocks.iter().any(|block| {
+        matches!(
+            block.terminator.kind,
+            TerminatorKind::Assert { msg: AssertMessage::Overflow(..), .. }
+        )
+    });
+    assert_eq!(has_overflow_assert, overflow_checks);
+    ControlFlow::Continue((overflow_checks, stable_mir::debug_assertions_enabled()))
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "overflow_checks_input.rs";
+    generate_input(&path).unwrap();
+    let args = |flags: &[&str]| {
+        let mut args = vec![
+            "rustc".to_string(),
+            "--crate-type=lib".to_string(),
+            "--crate-name".to_string(),
+            CRATE_NAME.to_string(),
+            path.to_string(),
+        ];
+        args.extend(flags.iter().map(|flag| flag.to_string()));
+        args
+    };
+    // Debug builds enable both by default.
+    assert_eq!(run!(args(&[]), read_flags).unwrap(), (true, true));
+    assert_eq!(run!(args(&["-Coverflow-checks=off"]), read_flags).unwrap(), (false, true));
+    assert_eq!(run!(args(&["-Copt-level=3"]), read_flags).unwrap(), (false, false));
+    assert_eq!(
+        run!(args(&["-Copt-level=3", "-Coverflow-checks=on"]), read_flags).unwrap(),
+        (true, false)
+    );
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn add(a: u8, b: u8) -> u8 {{
+        a + b
+    }}
+    "#
+    )?;
+    Ok(())
+}
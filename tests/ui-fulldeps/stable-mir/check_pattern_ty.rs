@@ -0,0 +1,113 @@
+//@ run-pass
+//! Test that users are able to inspect the pattern of a pattern type.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{Pattern, RigidTy, TyConst, TyConstKind, TyKind, UintTy};
+use stable_mir::visitor::{Visitable, Visitor};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "pattern_types").unwrap().body();
+    let [inclusive, open] = body.arg_locals() else { unreachable!() };
+
+    let TyKind::RigidTy(RigidTy::Pat(inner, pattern)) = inclusive.ty.kind() else {
+        unreachable!("Expected a pattern type, but found: {}", inclusive.ty)
+    };
+    assert_eq!(inner.kind(), TyKind::RigidTy(RigidTy::Uint(UintTy::U32)));
+    let Pattern::Range { start, end, include_end } = pattern;
+    assert_eq!(read_bound(&start.unwrap()), 1);
+    assert_eq!(read_bound(&end.unwrap()), 10);
+    assert!(include_end);
+
+    // The visitor should visit both bounds of the range.
+    let mut visitor = ConstCounter(0);
+    let _ = inclusive.ty.visit(&mut visitor);
+    assert_eq!(visitor.0, 2);
+
+    let TyKind::RigidTy(RigidTy::Pat(_, Pattern::Range { start, end, .. })) = open.ty.kind() else {
+        unreachable!("Expected a pattern type, but found: {}", open.ty)
+    };
+    assert_eq!(read_bound(&start.unwrap()), 1);
+    assert!(end.is_none());
+
+    let mut visitor = ConstCounter(0);
+    let _ = open.ty.visit(&mut visitor);
+    assert_eq!(visitor.0, 1);
+    ControlFlow::Continue(())
+}
+
+fn read_bound(bound: &TyConst) -> u128 {
+    let TyConstKind::Value(_, alloc) = bound.kind() else {
+        unreachable!("Expected a value, but found: {bound:?}")
+    };
+    alloc.read_uint().unwrap()
+}
+
+struct ConstCounter(usize);
+
+impl Visitor for ConstCounter {
+    type Break = ();
+
+    fn visit_const(&mut self, c: &TyConst) -> ControlFlow<Self::Break> {
+        self.0 += 1;
+        c.super_visit(self)
+    }
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "pattern_ty_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #![feature(pattern_types, pattern_type_macro)]
+    #![allow(incomplete_features)]
+    use std::pat::pattern_type;
+
+    pub fn pattern_types(_: pattern_type!(u32 is 1..=10), _: pattern_type!(u32 is 1..)) {{}}
+    "#
+    )?;
+    Ok(())
+}
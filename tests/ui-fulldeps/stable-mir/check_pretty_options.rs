@@ -0,0 +1,80 @@
+//@ run-pass
+//! Test that users are able to configure how MIR bodies are rendered.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::pretty::{PrettyOptions, render_with};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = items.iter().find(|item| item.trimmed_name() == "widen").unwrap().body();
+
+    // The default options match the output of `dump`.
+    let mut dump = Vec::new();
+    body.dump(&mut dump, "widen").unwrap();
+    let plain = render_with(&body, "widen", PrettyOptions::default());
+    assert_eq!(plain, String::from_utf8(dump).unwrap());
+    assert!(!plain.contains("//"));
+
+    let opts = PrettyOptions { local_types: true, ..PrettyOptions::default() };
+    let typed = render_with(&body, "widen", opts);
+    assert!(typed.contains("// _0: u16, _1: u8"), "{typed}");
+    assert_eq!(typed.lines().count(), plain.lines().count());
+
+    let opts = PrettyOptions { numbered: true, spans: true, ..PrettyOptions::default() };
+    let numbered = render_with(&body, "widen", opts);
+    assert!(numbered.lines().any(|line| line.trim_start().starts_with("0: ")), "{numbered}");
+    assert!(numbered.contains("widen_input.rs"), "{numbered}");
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "widen_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn widen(val: u8) -> u16 {{
+        val as u16
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,95 @@
+//@ run-pass
+//! Test that users are able to retrieve the allocations reachable from a static.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::alloc::GlobalAlloc;
+use stable_mir::mir::mono::StaticDef;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    // `CHAIN` points to the memory of the inner reference, which points to the value.
+    let chain = get_static(&items, "CHAIN");
+    let allocs = chain.reachable_allocs().unwrap();
+    assert_eq!(allocs.len(), 2, "Unexpected allocations: {allocs:?}");
+    let memory: Vec<_> = allocs
+        .iter()
+        .map(|(_, global_alloc)| {
+            let GlobalAlloc::Memory(alloc) = global_alloc else {
+                panic!("Expected memory, but found {global_alloc:?}")
+            };
+            alloc
+        })
+        .collect();
+    assert!(memory.iter().any(|alloc| alloc.provenance.ptrs.len() == 1));
+    assert!(memory.iter().any(|alloc| alloc.read_uint() == Ok(42)));
+
+    // Other statics are reported, but not traversed.
+    let allocs = get_static(&items, "REF_OTHER").reachable_allocs().unwrap();
+    assert_eq!(allocs.len(), 1, "Unexpected allocations: {allocs:?}");
+    let GlobalAlloc::Static(other) = &allocs[0].1 else { panic!("Expected a static") };
+    assert_eq!(other.name(), "OTHER");
+
+    // Statics without pointers don't reference any allocation.
+    assert!(get_static(&items, "OTHER").reachable_allocs().unwrap().is_empty());
+    ControlFlow::Continue(())
+}
+
+fn get_static(items: &CrateItems, name: &str) -> StaticDef {
+    let item = items.iter().find(|crate_item| crate_item.name() == name).unwrap();
+    StaticDef::try_from(item).unwrap()
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "reachable_allocs_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub static CHAIN: &&u32 = &&42;
+
+    pub static OTHER: u32 = 10;
+
+    pub static REF_OTHER: &u32 = &OTHER;
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,85 @@
+//@ run-pass
+//! Test that users are able to check whether a type is recursive.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = items.iter().find(|item| item.trimmed_name() == "types").unwrap().body();
+    let recursive: Vec<bool> =
+        body.arg_locals().iter().map(|local| local.ty.is_recursive()).collect();
+    // `List`, `Flat`, `Tree`, `Even`, `Wrapper<Flat>`, `u8`
+    assert_eq!(recursive, [true, false, true, true, false, false]);
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "recursive_ty_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub struct List {{
+        next: Option<Box<List>>,
+    }}
+
+    pub struct Flat {{
+        x: u8,
+    }}
+
+    pub struct Tree {{
+        children: Vec<Tree>,
+    }}
+
+    pub enum Even {{
+        Zero,
+        Next(Box<Odd>),
+    }}
+
+    pub struct Odd(Even);
+
+    pub struct Wrapper<T>(T);
+
+    pub fn types(_: List, _: Flat, _: Tree, _: Even, _: Wrapper<Flat>, _: u8) {{}}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,85 @@
+//@ run-pass
+//! Test that users are able to retrieve the mutability of reference and pointer types.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::Mutability;
+use stable_mir::ty::{Ty, UintTy};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = items.iter().find(|item| item.trimmed_name() == "pointers").unwrap().body();
+    let mutability: Vec<_> =
+        body.arg_locals().iter().map(|local| local.ty.ref_mutability()).collect();
+    assert_eq!(
+        mutability,
+        [
+            Some(Mutability::Mut),
+            Some(Mutability::Not),
+            Some(Mutability::Not),
+            Some(Mutability::Mut),
+            None,
+            None
+        ]
+    );
+
+    let ptr = Ty::new_ptr(Ty::unsigned_ty(UintTy::U8), Mutability::Mut);
+    assert_eq!(ptr.ref_mutability(), Some(Mutability::Mut));
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "ref_mutability_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn pointers(
+        _a: *mut u8,
+        _b: &u8,
+        _c: *const u16,
+        _d: &mut u16,
+        _e: u8,
+        _f: Box<u8>,
+    ) {{}}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,151 @@
+//@ run-pass
+//! Test that users are able to retrieve the body of compiler generated shims.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::mono::{Instance, InstanceKind};
+use stable_mir::mir::{
+    Body, CastKind, PointerCoercion, Rvalue, StatementKind, TerminatorKind,
+};
+use stable_mir::ty::{ClosureKind, RigidTy, Ty, TyKind, UintTy};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let drop_vec = get_item(&items, "drop_vec").unwrap();
+    check_drop_glue(Instance::try_from(*drop_vec).unwrap().body().unwrap());
+    let closure_ptr = get_item(&items, "closure_ptr").unwrap();
+    check_closure_shim(Instance::try_from(*closure_ptr).unwrap().body().unwrap());
+    ControlFlow::Continue(())
+}
+
+/// Check that the drop glue of `Vec<u8>` has a body, while `u8` has no drop glue.
+fn check_drop_glue(body: Body) {
+    let vec_ty = body.arg_locals()[0].ty;
+    let drop_glue = Instance::resolve_drop_in_place(vec_ty);
+    assert_eq!(drop_glue.kind, InstanceKind::Shim);
+    assert!(drop_glue.is_drop_glue());
+    assert!(!drop_glue.is_empty_shim());
+
+    let shim_body = drop_glue.drop_shim_body().unwrap();
+    let calls: Vec<_> = shim_body
+        .blocks
+        .iter()
+        .filter_map(|bb| match &bb.terminator.kind {
+            TerminatorKind::Call { func, .. } => {
+                let TyKind::RigidTy(RigidTy::FnDef(def, _)) =
+                    func.ty(shim_body.locals()).unwrap().kind()
+                else {
+                    unreachable!()
+                };
+                Some(def.name())
+            }
+            _ => None,
+        })
+        .collect();
+    assert!(
+        calls.iter().any(|name| name.ends_with("Drop>::drop")),
+        "Expected call to `Vec::drop`, but found: {calls:?}"
+    );
+
+    let no_glue = Instance::resolve_drop_in_place(Ty::unsigned_ty(UintTy::U8));
+    assert!(no_glue.is_empty_shim());
+    assert!(no_glue.drop_shim_body().is_none());
+}
+
+/// Check that the shim used to call a closure via a function pointer has a body.
+fn check_closure_shim(body: Body) {
+    let (def, args) = body
+        .blocks
+        .iter()
+        .flat_map(|bb| &bb.statements)
+        .find_map(|stmt| match &stmt.kind {
+            StatementKind::Assign(
+                _,
+                Rvalue::Cast(
+                    CastKind::PointerCoercion(PointerCoercion::ClosureFnPointer(_)),
+                    op,
+                    _,
+                ),
+            ) => {
+                let TyKind::RigidTy(RigidTy::Closure(def, args)) =
+                    op.ty(body.locals()).unwrap().kind()
+                else {
+                    unreachable!()
+                };
+                Some((def, args))
+            }
+            _ => None,
+        })
+        .expect("Expected closure to fn pointer coercion");
+
+    let shim = Instance::resolve_closure(def, &args, ClosureKind::FnOnce).unwrap();
+    assert_eq!(shim.kind, InstanceKind::Shim);
+    assert!(!shim.is_drop_glue());
+    assert!(shim.has_body());
+    let shim_body = shim.body().unwrap();
+    assert!(!shim_body.blocks.is_empty());
+
+    let closure = Instance::resolve_closure(def, &args, ClosureKind::Fn).unwrap();
+    assert_eq!(closure.kind, InstanceKind::Item);
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "shims_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "-Cpanic=abort".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn drop_vec(v: Vec<u8>) {{
+        drop(v);
+    }}
+
+    pub fn closure_ptr() -> fn(u8) -> u8 {{
+        let ptr: fn(u8) -> u8 = |x| x + 1;
+        ptr
+    }}
+    "#
+    )?;
+    Ok(())
+}
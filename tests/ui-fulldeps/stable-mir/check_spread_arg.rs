@@ -0,0 +1,98 @@
+//@ run-pass
+//! Test that users are able to expand the spread argument of "rust-call" functions.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{RigidTy, Ty, TyKind, UintTy};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let u8_ty = Ty::unsigned_ty(UintTy::U8);
+    let u16_ty = Ty::unsigned_ty(UintTy::U16);
+
+    let call_mut = items.iter().find(|item| item.name().ends_with("::call_mut")).unwrap();
+    let body = call_mut.body();
+    assert_eq!(body.spread_arg(), Some(2));
+    let arg_tys = body.expanded_arg_tys();
+    assert_eq!(arg_tys.len(), 3);
+    assert!(matches!(arg_tys[0].kind(), TyKind::RigidTy(RigidTy::Ref(..))));
+    assert_eq!(&arg_tys[1..], &[u8_ty, u16_ty]);
+
+    // Other functions take their arguments as declared.
+    let body = get_item(&items, "add").unwrap().body();
+    assert_eq!(body.spread_arg(), None);
+    assert_eq!(body.expanded_arg_tys(), vec![Ty::new_tuple(&[u8_ty, u16_ty])]);
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "spread_arg_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #![feature(unboxed_closures, fn_traits)]
+
+    pub struct Adder;
+
+    impl FnOnce<(u8, u16)> for Adder {{
+        type Output = u32;
+        extern "rust-call" fn call_once(mut self, args: (u8, u16)) -> u32 {{
+            self.call_mut(args)
+        }}
+    }}
+
+    impl FnMut<(u8, u16)> for Adder {{
+        extern "rust-call" fn call_mut(&mut self, args: (u8, u16)) -> u32 {{
+            add(args)
+        }}
+    }}
+
+    pub fn add(args: (u8, u16)) -> u32 {{
+        args.0 as u32 + args.1 as u32
+    }}
+    "#
+    )?;
+    Ok(())
+}
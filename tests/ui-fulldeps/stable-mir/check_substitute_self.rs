@@ -0,0 +1,124 @@
+//@ run-pass
+//! Test that users are able to replace the `Self` type in the body of a trait method.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::TerminatorKind;
+use stable_mir::mir::mono::Instance;
+use stable_mir::ty::{GenericArgKind, GenericArgs, ParamTy, RigidTy, Ty, TyKind, UintTy};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let pair = get_item(&items, "Pair::pair");
+    let generic = pair.body();
+    let self_ty = pointee(generic.arg_locals()[0].ty);
+    assert!(matches!(self_ty.kind(), TyKind::Param(ParamTy { index: 0, .. })), "{self_ty:?}");
+
+    let u8_ty = Ty::unsigned_ty(UintTy::U8);
+    let body = generic.substitute_self(u8_ty).unwrap();
+    assert_eq!(body.ret_local().ty, Ty::new_tuple(&[u8_ty, u8_ty]));
+    assert_eq!(pointee(body.arg_locals()[0].ty), u8_ty);
+
+    // `Self` is also replaced in the constants of the body, such as the function that is called.
+    let (def, args) = body
+        .blocks
+        .iter()
+        .find_map(|bb| match &bb.terminator.kind {
+            TerminatorKind::Call { func, .. } => match func.ty(body.locals()).unwrap().kind() {
+                TyKind::RigidTy(RigidTy::FnDef(def, args)) => Some((def, args)),
+                _ => None,
+            },
+            _ => None,
+        })
+        .expect("Expected a call to `Clone::clone`");
+    assert_eq!(args.0, [GenericArgKind::Type(u8_ty)]);
+    assert!(Instance::resolve(def, &args).is_ok());
+
+    // The result matches the body of the instance for `u8`.
+    let TyKind::RigidTy(RigidTy::FnDef(pair_def, _)) = pair.ty().kind() else {
+        panic!("Expected a function definition")
+    };
+    let pair_args = GenericArgs(vec![GenericArgKind::Type(u8_ty)]);
+    let mono = Instance::resolve(pair_def, &pair_args).unwrap().body().unwrap();
+    assert_eq!(body.local_types(), mono.local_types());
+
+    // The replacement must be a concrete type.
+    assert!(generic.substitute_self(self_ty).is_err());
+
+    // Other type parameters are kept as is.
+    let free = get_item(&items, "free").body();
+    let unchanged = free.substitute_self(u8_ty).unwrap();
+    assert_eq!(unchanged.local_types(), free.local_types());
+    assert_eq!(unchanged.blocks, free.blocks);
+    ControlFlow::Continue(())
+}
+
+fn pointee(ty: Ty) -> Ty {
+    let TyKind::RigidTy(RigidTy::Ref(_, pointee, _)) = ty.kind() else {
+        panic!("Expected a reference, but found {ty:?}")
+    };
+    pointee
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> &'a CrateItem {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name).unwrap()
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "substitute_self_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub trait Pair: Clone {{
+        fn pair(&self) -> (Self, Self) {{
+            (self.clone(), self.clone())
+        }}
+    }}
+
+    impl Pair for u8 {{}}
+
+    pub fn free<T: Clone>(value: &T) -> T {{
+        value.clone()
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,115 @@
+//@ run-pass
+//! Test that users are able to evaluate the targets of a `SwitchInt` terminator.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{
+    BasicBlockIdx, Body, Operand, Rvalue, StatementKind, SwitchTargets, TerminatorKind,
+};
+use stable_mir::ty::ConstantKind;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "three_way").unwrap().body();
+    let TerminatorKind::SwitchInt { targets, .. } = &body.blocks[0].terminator.kind else {
+        unreachable!("Expected a switch, but found: {:?}", body.blocks[0].terminator)
+    };
+    assert_eq!(targets.len(), 3);
+
+    let branches: Vec<_> = targets.branches().collect();
+    assert_eq!(branches.iter().map(|(val, _)| *val).collect::<Vec<_>>(), vec![0, 1]);
+    for (val, target) in branches {
+        assert_eq!(targets.target_for_value(val), target);
+    }
+    assert_eq!(targets.target_for_value(2), targets.otherwise());
+    assert_eq!(targets.target_for_value(u128::MAX), targets.otherwise());
+
+    assert_eq!(returned_value(&body, targets, 0), 10);
+    assert_eq!(returned_value(&body, targets, 1), 20);
+    assert_eq!(returned_value(&body, targets, 42), 30);
+
+    // The first matching branch wins.
+    let dup = SwitchTargets::new(vec![(5, 1), (5, 2)], 3);
+    assert_eq!(dup.target_for_value(5), 1);
+    assert_eq!(dup.target_for_value(6), 3);
+    ControlFlow::Continue(())
+}
+
+/// Follow the switch for the given value, and retrieve the constant assigned to the return local.
+fn returned_value(body: &Body, targets: &SwitchTargets, value: u128) -> u128 {
+    let target: BasicBlockIdx = targets.target_for_value(value);
+    body.blocks[target]
+        .statements
+        .iter()
+        .find_map(|stmt| match &stmt.kind {
+            StatementKind::Assign(place, Rvalue::Use(Operand::Constant(constant)))
+                if place.local == 0 =>
+            {
+                let ConstantKind::Allocated(alloc) = constant.const_.kind() else {
+                    unreachable!()
+                };
+                Some(alloc.read_uint().unwrap())
+            }
+            _ => None,
+        })
+        .expect("Expected an assignment to the return local")
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "switch_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn three_way(x: u8) -> u8 {{
+        match x {{
+            0 => 10,
+            1 => 20,
+            _ => 30,
+        }}
+    }}
+    "#
+    )?;
+    Ok(())
+}
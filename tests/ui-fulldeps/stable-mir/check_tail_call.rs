@@ -0,0 +1,131 @@
+//@ run-pass
+//! Test that users are able to inspect explicit tail calls.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::visit::{Location, MirVisitor};
+use stable_mir::mir::{LocalDecl, Operand, Terminator, TerminatorKind};
+use stable_mir::ty::{RigidTy, TyKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "caller").unwrap().body();
+
+    let tail_calls: Vec<_> = body
+        .blocks
+        .iter()
+        .filter(|bb| matches!(bb.terminator.kind, TerminatorKind::TailCall { .. }))
+        .collect();
+    assert_eq!(tail_calls.len(), 1);
+    assert!(tail_calls[0].terminator.successors().is_empty());
+    assert!(tail_calls[0].terminator.kind.unwind().is_none());
+    assert!(
+        !body.blocks.iter().any(|bb| matches!(bb.terminator.kind, TerminatorKind::Call { .. })),
+        "Expected no regular call"
+    );
+
+    let mut visitor =
+        TailCallVisitor { locals: body.locals(), callees: vec![], operands: 0, in_call: false };
+    visitor.visit_body(&body);
+    assert_eq!(visitor.callees.len(), 1);
+    assert!(visitor.callees[0].ends_with("callee"), "Unexpected callee: {:?}", visitor.callees);
+    // The function and its argument.
+    assert_eq!(visitor.operands, 2);
+
+    let mut output = Vec::new();
+    body.dump(&mut output, "caller").unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("tailcall "), "Unexpected body: {output}");
+    ControlFlow::Continue(())
+}
+
+struct TailCallVisitor<'a> {
+    locals: &'a [LocalDecl],
+    callees: Vec<String>,
+    operands: usize,
+    in_call: bool,
+}
+
+impl<'a> MirVisitor for TailCallVisitor<'a> {
+    fn visit_terminator(&mut self, term: &Terminator, location: Location) {
+        if let TerminatorKind::TailCall { func, .. } = &term.kind {
+            let TyKind::RigidTy(RigidTy::FnDef(def, _)) = func.ty(self.locals).unwrap().kind()
+            else {
+                unreachable!()
+            };
+            self.callees.push(def.name());
+            self.in_call = true;
+            self.super_terminator(term, location);
+            self.in_call = false;
+        }
+    }
+
+    fn visit_operand(&mut self, operand: &Operand, location: Location) {
+        if self.in_call {
+            self.operands += 1;
+        }
+        self.super_operand(operand, location);
+    }
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "tail_call_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #![feature(explicit_tail_calls)]
+    #![allow(incomplete_features)]
+
+    pub fn caller(x: u32) -> u32 {{
+        become callee(x)
+    }}
+
+    pub fn callee(x: u32) -> u32 {{
+        x
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//@ run-pass
+//! Test that users are able to index the trait implementations by trait.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{ImplDef, TraitDef};
+use stable_mir::*;
+use std::collections::HashSet;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let local_impls = stable_mir::local_crate().trait_impls();
+    let local_clone_impls: HashSet<ImplDef> = local_impls
+        .iter()
+        .copied()
+        .filter(|impl_def| trait_of(impl_def).trimmed_name() == "Clone")
+        .collect();
+    assert_eq!(local_clone_impls.len(), 2);
+    let clone_trait = trait_of(local_clone_impls.iter().next().unwrap());
+
+    let index = stable_mir::trait_impl_index();
+    let clone_impls = &index[&clone_trait];
+    assert!(clone_impls.iter().all(|impl_def| trait_of(impl_def) == clone_trait));
+    assert!(local_clone_impls.iter().all(|impl_def| clone_impls.contains(impl_def)));
+
+    // Every implementation shows up exactly once in the index.
+    let all_impls = stable_mir::all_trait_impls();
+    assert_eq!(index.values().map(Vec::len).sum::<usize>(), all_impls.len());
+    for impl_def in &local_impls {
+        assert!(index[&trait_of(impl_def)].contains(impl_def));
+    }
+    ControlFlow::Continue(())
+}
+
+fn trait_of(impl_def: &ImplDef) -> TraitDef {
+    impl_def.trait_impl().value.def_id
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "trait_impl_index_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #[derive(Clone)]
+    pub struct Derived(u8);
+
+    pub struct Manual(u16);
+
+    impl Clone for Manual {{
+        fn clone(&self) -> Self {{
+            Manual(self.0)
+        }}
+    }}
+
+    impl std::fmt::Display for Manual {{
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
+            write!(f, "{{}}", self.0)
+        }}
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,143 @@
+//@ run-pass
+//! Test that users are able to retrieve the traits and vtable slot of trait upcasts.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{Body, CastKind, PointerCoercion, Rvalue, StatementKind};
+use stable_mir::ty::{Ty, TraitUpcast};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let (source, target) = unsize_cast(&get_item(&items, "to_super").unwrap().body());
+    let upcast = source.trait_upcast(target).unwrap();
+    assert_eq!(trait_names(&upcast), ("Sub".to_string(), "Super".to_string()));
+    // The first supertrait shares the vtable of the subtrait.
+    assert_eq!(source.supertrait_vtable_slot(target), Ok(None));
+
+    let (source, target) = unsize_cast(&get_item(&items, "to_other").unwrap().body());
+    let upcast = source.trait_upcast(target).unwrap();
+    assert_eq!(trait_names(&upcast), ("Sub".to_string(), "Other".to_string()));
+    assert!(matches!(source.supertrait_vtable_slot(target), Ok(Some(_))));
+
+    // Unsizing a concrete type is not an upcast.
+    let (source, target) = unsize_cast(&get_item(&items, "to_dyn").unwrap().body());
+    assert_eq!(source.trait_upcast(target), None);
+    assert!(target.supertrait_vtable_slot(source).is_err());
+    ControlFlow::Continue(())
+}
+
+/// Return the source and target pointee types of the only unsizing cast in the body.
+fn unsize_cast(body: &Body) -> (Ty, Ty) {
+    body.blocks
+        .iter()
+        .flat_map(|bb| &bb.statements)
+        .find_map(|stmt| match &stmt.kind {
+            StatementKind::Assign(
+                _,
+                Rvalue::Cast(CastKind::PointerCoercion(PointerCoercion::Unsize), op, target),
+            ) => {
+                let source = op.ty(body.locals()).unwrap();
+                Some((pointee(source), pointee(*target)))
+            }
+            _ => None,
+        })
+        .unwrap()
+}
+
+fn pointee(ty: Ty) -> Ty {
+    ty.kind().builtin_deref(true).unwrap().ty
+}
+
+fn trait_names(upcast: &TraitUpcast) -> (String, String) {
+    (upcast.source.value.def_id.trimmed_name(), upcast.target.value.def_id.trimmed_name())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "trait_upcast_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #![feature(trait_upcasting)]
+
+    pub trait Super {{
+        fn name(&self) -> u8;
+    }}
+
+    pub trait Other {{
+        fn other(&self) -> u8;
+    }}
+
+    pub trait Sub: Super + Other {{
+        fn sub(&self) -> u8;
+    }}
+
+    impl Super for u8 {{
+        fn name(&self) -> u8 {{ 0 }}
+    }}
+
+    impl Other for u8 {{
+        fn other(&self) -> u8 {{ 1 }}
+    }}
+
+    impl Sub for u8 {{
+        fn sub(&self) -> u8 {{ 2 }}
+    }}
+
+    pub fn to_super(sub: &dyn Sub) -> &dyn Super {{
+        sub
+    }}
+
+    pub fn to_other(sub: &dyn Sub) -> &dyn Other {{
+        sub
+    }}
+
+    pub fn to_dyn(val: &u8) -> &dyn Sub {{
+        val
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,97 @@
+//@ run-pass
+//! Test that users are able to check whether a type can be transmuted into another.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{CastKind, Rvalue, StatementKind};
+use stable_mir::ty::{RigidTy, Ty, UintTy};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let u8_ty = Ty::unsigned_ty(UintTy::U8);
+    let u32_ty = Ty::unsigned_ty(UintTy::U32);
+    let u64_ty = Ty::unsigned_ty(UintTy::U64);
+    let bytes_ty = Ty::try_new_array(u8_ty, 4).unwrap();
+
+    assert!(u32_ty.transmutable_to(&bytes_ty).unwrap());
+    assert!(bytes_ty.transmutable_to(&u32_ty).unwrap());
+    assert!(!u32_ty.transmutable_to(&u64_ty).unwrap());
+    assert!(!u64_ty.transmutable_to(&u32_ty).unwrap());
+
+    // Unsized types cannot be transmuted.
+    let str_ty = Ty::from_rigid_kind(RigidTy::Str);
+    assert!(!str_ty.transmutable_to(&str_ty).unwrap());
+
+    // Check the cast generated for a call to `transmute`.
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "to_bytes").unwrap().body();
+    let (src, dst) = body
+        .blocks
+        .iter()
+        .flat_map(|bb| &bb.statements)
+        .find_map(|stmt| match &stmt.kind {
+            StatementKind::Assign(place, Rvalue::Cast(CastKind::Transmute, op, _)) => {
+                Some((op.ty(body.locals()).unwrap(), place.ty(body.locals()).unwrap()))
+            }
+            _ => None,
+        })
+        .expect("Expected a transmute cast");
+    assert_eq!(src, u32_ty);
+    assert_eq!(dst, bytes_ty);
+    assert!(src.transmutable_to(&dst).unwrap());
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "transmute_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn to_bytes(x: u32) -> [u8; 4] {{
+        unsafe {{ std::mem::transmute(x) }}
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,101 @@
+//@ run-pass
+//! Test that users are able to retrieve the inner field type of transparent types.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{RigidTy, Ty, TyKind, UintTy};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let u32_ty = Ty::unsigned_ty(UintTy::U32);
+    assert_eq!(transparent_field_of(&items, "wrapper"), Some(u32_ty));
+
+    // Zero-sized fields are ignored, and generic fields are instantiated.
+    let u64_ty = Ty::unsigned_ty(UintTy::U64);
+    assert_eq!(transparent_field_of(&items, "tagged"), Some(u64_ty));
+
+    assert_eq!(transparent_field_of(&items, "plain"), None);
+    ControlFlow::Continue(())
+}
+
+/// Get the transparent field type of the type returned by the function with the given name.
+fn transparent_field_of(items: &CrateItems, name: &str) -> Option<Ty> {
+    let item = items.iter().find(|item| item.trimmed_name() == name).unwrap();
+    let ret_ty = item.body().ret_local().ty;
+    let TyKind::RigidTy(RigidTy::Adt(def, args)) = ret_ty.kind() else {
+        panic!("Expected an ADT, but found {ret_ty:?}")
+    };
+    def.transparent_field_ty(&args)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "transparent_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    use std::marker::PhantomData;
+
+    #[repr(transparent)]
+    pub struct Wrapper(u32);
+
+    #[repr(transparent)]
+    pub struct Tagged<T> {{
+        _tag: PhantomData<u8>,
+        value: T,
+    }}
+
+    pub struct Plain(u32);
+
+    pub fn wrapper() -> Wrapper {{
+        Wrapper(1)
+    }}
+
+    pub fn tagged() -> Tagged<u64> {{
+        Tagged {{ _tag: PhantomData, value: 1 }}
+    }}
+
+    pub fn plain() -> Plain {{
+        Plain(1)
+    }}
+    "#
+    )?;
+    Ok(())
+}
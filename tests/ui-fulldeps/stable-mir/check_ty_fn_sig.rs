@@ -0,0 +1,98 @@
+//@ run-pass
+//! Test that users are able to retrieve the signature of function types.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::TerminatorKind;
+use stable_mir::ty::{Ty, UintTy};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "apply").unwrap().body();
+
+    // The argument is a function pointer `fn(u8) -> bool`.
+    let fn_ptr = body.arg_locals()[0].ty;
+    let sig = fn_ptr.fn_sig().unwrap().value;
+    assert_eq!(sig.inputs(), &[Ty::unsigned_ty(UintTy::U8)]);
+    assert_eq!(sig.output(), Ty::bool_ty());
+
+    // The callee of `generic::<u16>` is a function definition.
+    let callee = body
+        .blocks
+        .iter()
+        .find_map(|bb| match &bb.terminator.kind {
+            TerminatorKind::Call { func, .. } => {
+                let ty = func.ty(body.locals()).unwrap();
+                ty.kind().is_fn().then_some(ty)
+            }
+            _ => None,
+        })
+        .expect("Expected a call to a function definition");
+    let sig = callee.fn_sig().unwrap().value;
+    assert_eq!(sig.inputs(), &[Ty::unsigned_ty(UintTy::U16)]);
+    assert_eq!(sig.output(), Ty::unsigned_ty(UintTy::U16));
+
+    // Other types have no signature.
+    assert_eq!(Ty::bool_ty().fn_sig(), None);
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "ty_fn_sig_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn apply(f: fn(u8) -> bool, value: u8) -> bool {{
+        f(value) && generic(value as u16) > 0
+    }}
+
+    #[inline(never)]
+    fn generic<T: Copy>(value: T) -> T {{
+        value
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -19,7 +19,7 @@
 extern crate stable_mir;
 
 use rustc_smir::rustc_internal;
-use stable_mir::ty::{RigidTy, TyKind, Ty, };
+use stable_mir::ty::{IndexedVal, RigidTy, TyKind, Ty, };
 use stable_mir::mir::{Body, MirVisitor, FieldIdx, Place, ProjectionElem, visit::{Location,
                                                                             PlaceContext}};
 use std::io::Write;
@@ -49,7 +49,7 @@ fn check_tys(local_ty: Ty, idx: FieldIdx, expected_ty: Ty) {
     let TyKind::RigidTy(RigidTy::Adt(def, args)) = local_ty.kind() else { unreachable!() };
     assert_eq!(def.ty_with_args(&args), local_ty);
 
-    let field_def = &def.variants_iter().next().unwrap().fields()[idx];
+    let field_def = &def.variants_iter().next().unwrap().fields()[idx.to_index()];
     let field_ty = field_def.ty_with_args(&args);
     assert_eq!(field_ty, expected_ty);
 
@@ -0,0 +1,97 @@
+//@ run-pass
+//! Test that users are able to build the dependency graph between types.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{AdtDef, TypeGraph};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let entry = items.iter().find(|item| item.trimmed_name() == "entry").unwrap();
+    let graph = stable_mir::ty::type_graph(&[*entry]);
+
+    let a = get_adt(&graph, "A");
+    let b = get_adt(&graph, "B");
+    assert!(graph.has_edge(a, b));
+    assert!(!graph.has_edge(b, a));
+    assert!(graph.successors(b).is_empty());
+
+    // Recursive types have an edge to themselves.
+    let node = get_adt(&graph, "Node");
+    assert!(graph.has_edge(node, node));
+    assert!(graph.has_edge(node, get_adt(&graph, "Option")));
+    assert!(graph.has_edge(node, get_adt(&graph, "Box")));
+
+    // Types that are not used by the item are not part of the graph.
+    assert!(graph.nodes().all(|adt| adt.trimmed_name() != "Unused"));
+    ControlFlow::Continue(())
+}
+
+fn get_adt(graph: &TypeGraph, name: &str) -> AdtDef {
+    graph.nodes().find(|adt| adt.trimmed_name() == name).unwrap()
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "type_graph_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub struct A {{
+        pub b: B,
+    }}
+
+    pub struct B;
+
+    pub struct Node {{
+        pub value: u32,
+        pub next: Option<Box<Node>>,
+    }}
+
+    pub struct Unused(A);
+
+    pub fn entry(a: A, node: Node) -> u32 {{
+        let _b = a.b;
+        node.value
+    }}
+    "#
+    )?;
+    Ok(())
+}
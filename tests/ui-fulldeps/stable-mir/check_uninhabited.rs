@@ -0,0 +1,98 @@
+//@ run-pass
+//! Test that users are able to check whether a type is uninhabited.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{RigidTy, Ty, TyKind, UintTy};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    // The return type of `never` is `!`.
+    let never_ty = get_item(&items, "never").unwrap().body().ret_local().ty;
+    assert_eq!(never_ty.kind(), TyKind::RigidTy(RigidTy::Never));
+    assert!(never_ty.is_uninhabited());
+
+    let body = get_item(&items, "uninhabited").unwrap().body();
+    let [void_ty, tuple_ty, wrapper_ty] = body.arg_locals() else { unreachable!() };
+    assert!(void_ty.ty.is_uninhabited());
+    assert!(tuple_ty.ty.is_uninhabited());
+    // The field of `Wrapper` is private, but that should not affect the result.
+    assert!(wrapper_ty.ty.is_uninhabited());
+
+    let u8_ty = Ty::unsigned_ty(UintTy::U8);
+    assert!(!u8_ty.is_uninhabited());
+    assert!(!Ty::new_tuple(&[]).is_uninhabited());
+    assert!(Ty::new_tuple(&[u8_ty, void_ty.ty]).is_uninhabited());
+
+    let body = get_item(&items, "inhabited").unwrap().body();
+    for local in body.arg_locals() {
+        assert!(!local.ty.is_uninhabited(), "Expected inhabited type: {}", local.ty);
+    }
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "uninhabited_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub enum Void {{}}
+
+    mod inner {{
+        pub struct Wrapper(super::Void);
+    }}
+
+    pub fn never() -> ! {{
+        loop {{}}
+    }}
+
+    pub fn uninhabited(_: Void, _: (u8, Void), _: inner::Wrapper) {{}}
+
+    pub fn inhabited(_: u8, _: Option<Void>, _: &Void, _: [Void; 0]) {{}}
+    "#
+    )?;
+    Ok(())
+}
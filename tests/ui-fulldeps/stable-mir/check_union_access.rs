@@ -0,0 +1,110 @@
+//@ run-pass
+//! Test that users are able to check whether a place accesses a union field.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::visit::{Location, PlaceContext, PlaceRef};
+use stable_mir::mir::{LocalDecl, MirVisitor, Place};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    assert!(has_union_access(get_item(&items, "read_union").unwrap()));
+    assert!(has_union_access(get_item(&items, "read_nested").unwrap()));
+    assert!(!has_union_access(get_item(&items, "read_struct").unwrap()));
+    ControlFlow::Continue(())
+}
+
+/// Whether any place used in the body of the given item accesses a union field.
+fn has_union_access(item: &CrateItem) -> bool {
+    let body = item.body();
+    let mut visitor = UnionAccessVisitor { locals: body.locals(), found: false };
+    visitor.visit_body(&body);
+    visitor.found
+}
+
+struct UnionAccessVisitor<'a> {
+    locals: &'a [LocalDecl],
+    found: bool,
+}
+
+impl MirVisitor for UnionAccessVisitor<'_> {
+    fn visit_place(&mut self, place: &Place, ptx: PlaceContext, location: Location) {
+        let place_ref = PlaceRef { local: place.local, projection: &place.projection };
+        self.found |= place_ref.is_union_access(self.locals);
+        self.super_place(place, ptx, location)
+    }
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "union_access_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    #[derive(Clone, Copy)]
+    pub struct Pair {{
+        pub first: u32,
+        pub second: u32,
+    }}
+
+    pub union Bits {{
+        pub int: u32,
+        pub pair: Pair,
+    }}
+
+    pub fn read_union(bits: Bits) -> u32 {{
+        unsafe {{ bits.int }}
+    }}
+
+    pub fn read_nested(bits: &Bits) -> u32 {{
+        unsafe {{ bits.pair.second }}
+    }}
+
+    pub fn read_struct(pair: Pair) -> u32 {{
+        pair.first
+    }}
+    "#
+    )?;
+    Ok(())
+}
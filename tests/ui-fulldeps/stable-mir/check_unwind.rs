@@ -0,0 +1,100 @@
+//@ run-pass
+//! Test that users are able to check whether a terminator can unwind.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{Terminator, TerminatorKind, UnwindAction};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "caller").unwrap().body();
+
+    // Nothing can unwind, since the crate is compiled with `panic=abort`.
+    assert!(body.blocks.iter().all(|bb| !bb.terminator.can_unwind()));
+    let call = body
+        .blocks
+        .iter()
+        .map(|bb| &bb.terminator)
+        .find(|term| matches!(term.kind, TerminatorKind::Call { .. }))
+        .expect("Expected a call");
+    let TerminatorKind::Call { unwind, .. } = &call.kind else { unreachable!() };
+    assert_eq!(*unwind, UnwindAction::Unreachable);
+    assert!(!call.can_unwind());
+
+    assert!(with_unwind(call, UnwindAction::Continue).can_unwind());
+    assert!(with_unwind(call, UnwindAction::Cleanup(0)).can_unwind());
+    assert!(!with_unwind(call, UnwindAction::Terminate).can_unwind());
+
+    // Terminators without an unwind action cannot unwind.
+    let ret = Terminator { kind: TerminatorKind::Return, span: call.span };
+    assert!(!ret.can_unwind());
+    ControlFlow::Continue(())
+}
+
+fn with_unwind(term: &Terminator, action: UnwindAction) -> Terminator {
+    let mut term = term.clone();
+    let TerminatorKind::Call { unwind, .. } = &mut term.kind else { unreachable!() };
+    *unwind = action;
+    term
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "unwind_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "-Cpanic=abort".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn caller(v: Vec<u8>) -> usize {{
+        callee(&v)
+    }}
+
+    #[inline(never)]
+    fn callee(v: &[u8]) -> usize {{
+        v.len()
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,151 @@
+//@ run-pass
+//! Test that users are able to retrieve the upstream crate that contains a monomorphized instance.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::TerminatorKind;
+use stable_mir::mir::mono::Instance;
+use stable_mir::ty::{RigidTy, TyKind};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+const UPSTREAM_NAME: &str = "upstream";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    // The upstream crate has already generated `generic::<u32>`.
+    let instance = called_instance(get_item(&items, "shared").unwrap());
+    let krate = upstream_monomorphizations(&instance).unwrap();
+    assert_eq!(krate.name, UPSTREAM_NAME);
+    assert!(!krate.is_local);
+
+    // Nobody has generated `generic::<u64>` yet.
+    let instance = called_instance(get_item(&items, "not_shared").unwrap());
+    assert_eq!(upstream_monomorphizations(&instance), None);
+
+    // Local items are never monomorphized upstream.
+    let instance = called_instance(get_item(&items, "local_caller").unwrap());
+    assert_eq!(upstream_monomorphizations(&instance), None);
+    ControlFlow::Continue(())
+}
+
+/// Resolve the instance called by the first call terminator of the given item.
+fn called_instance(item: &CrateItem) -> Instance {
+    let body = item.body();
+    body.blocks
+        .iter()
+        .find_map(|bb| match &bb.terminator.kind {
+            TerminatorKind::Call { func, .. } => {
+                let TyKind::RigidTy(RigidTy::FnDef(def, args)) =
+                    func.ty(body.locals()).unwrap().kind()
+                else {
+                    unreachable!()
+                };
+                Some(Instance::resolve(def, &args).unwrap())
+            }
+            _ => None,
+        })
+        .expect("Expected a function call")
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// Compile the upstream crate normally, so the input crate can depend on it.
+fn compile_upstream() -> ControlFlow<()> {
+    ControlFlow::Continue(())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let upstream_path = "upstream_mono_dep.rs";
+    generate_upstream(&upstream_path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=rlib".to_string(),
+        "--crate-name".to_string(),
+        UPSTREAM_NAME.to_string(),
+        "-Zshare-generics=yes".to_string(),
+        upstream_path.to_string(),
+    ];
+    run!(args, compile_upstream).unwrap();
+
+    let path = "upstream_mono_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        "-Zshare-generics=yes".to_string(),
+        "--extern".to_string(),
+        format!("{UPSTREAM_NAME}=lib{UPSTREAM_NAME}.rlib"),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_upstream(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn generic<T: Default>() -> T {{
+        T::default()
+    }}
+
+    pub fn uses_u32() -> u32 {{
+        generic::<u32>()
+    }}
+    "#
+    )?;
+    Ok(())
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn shared() -> u32 {{
+        upstream::generic::<u32>()
+    }}
+
+    pub fn not_shared() -> u64 {{
+        upstream::generic::<u64>()
+    }}
+
+    fn local<T: Default>() -> T {{
+        T::default()
+    }}
+
+    pub fn local_caller() -> u32 {{
+        local::<u32>()
+    }}
+    "#
+    )?;
+    Ok(())
+}
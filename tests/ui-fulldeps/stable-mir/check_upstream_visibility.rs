@@ -0,0 +1,144 @@
+//@ run-pass
+//! Test that the exported items of an upstream crate take private modules into account.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::FnDef;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+const UPSTREAM_NAME: &str = "upstream";
+
+/// This function uses the Stable MIR APIs to check which items of an upstream crate are exported.
+fn test_stable_mir() -> ControlFlow<()> {
+    let krate = stable_mir::find_crates(UPSTREAM_NAME).pop().unwrap();
+    assert!(!krate.is_local);
+    let fns = krate.fn_defs();
+
+    assert!(get_fn(&fns, "::public_fn").is_exported());
+    assert!(get_fn(&fns, "::public::visible").is_exported());
+    assert!(!get_fn(&fns, "::in_crate").is_exported());
+
+    // `pub` items in a private module are only exported if they are re-exported.
+    let hidden = get_fn(&fns, "::hidden");
+    assert_eq!(hidden.visibility(), Visibility::Public);
+    assert!(!hidden.is_exported());
+    assert!(get_fn(&fns, "::reexported").is_exported());
+
+    // Methods are exported if the type that implements them is.
+    assert!(get_fn(&fns, "Exported::method").is_exported());
+    assert!(!get_fn(&fns, "Hidden::method").is_exported());
+    ControlFlow::Continue(())
+}
+
+fn get_fn(fns: &[FnDef], suffix: &str) -> CrateItem {
+    let Some(def) = fns.iter().find(|def| def.name().ends_with(suffix)) else {
+        panic!("Expected a function ending with `{suffix}`, but found {fns:?}")
+    };
+    CrateItem(def.def_id())
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let upstream_path = "upstream_visibility_input.rs";
+    let upstream_metadata = format!("lib{UPSTREAM_NAME}.rmeta");
+    generate_upstream_input(&upstream_path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        UPSTREAM_NAME.to_string(),
+        "--emit=metadata".to_string(),
+        "-o".to_string(),
+        upstream_metadata.clone(),
+        upstream_path.to_string(),
+    ];
+    run!(args, || ControlFlow::<()>::Continue(())).unwrap();
+
+    let path = "visibility_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        "--extern".to_string(),
+        format!("{UPSTREAM_NAME}={upstream_metadata}"),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_upstream_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn public_fn() {{
+        private::hidden();
+        Hidden.method();
+    }}
+
+    mod private {{
+        pub fn hidden() {{}}
+
+        pub fn reexported() {{}}
+    }}
+
+    pub use private::reexported;
+
+    pub mod public {{
+        pub fn visible() {{}}
+
+        pub(crate) fn in_crate() {{}}
+    }}
+
+    pub struct Exported;
+
+    impl Exported {{
+        pub fn method(&self) {{}}
+    }}
+
+    struct Hidden;
+
+    impl Hidden {{
+        pub fn method(&self) {{}}
+    }}
+    "#
+    )?;
+    Ok(())
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    extern crate upstream;
+
+    pub fn call() {{
+        upstream::public_fn();
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,87 @@
+//@ run-pass
+//! Test that users are able to retrieve the range of valid values of a type.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::abi::WrappingRange;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "niches").unwrap().body();
+    let [non_zero, option, byte, boolean, pair] = body.arg_locals() else {
+        unreachable!("Unexpected arguments")
+    };
+
+    assert_eq!(non_zero.ty.valid_range(), Some(WrappingRange { start: 1, end: 255 }));
+    // `None` is stored in the niche of `NonZeroU8`, so every value is valid.
+    let option_range = option.ty.valid_range().unwrap();
+    assert_eq!(option.ty.layout().unwrap().shape().size.bytes(), 1);
+    assert!(option_range.contains(0) && option_range.contains(255));
+
+    assert_eq!(byte.ty.valid_range(), Some(WrappingRange { start: 0, end: 255 }));
+    assert_eq!(boolean.ty.valid_range(), Some(WrappingRange { start: 0, end: 1 }));
+    // Only single scalars have a valid range.
+    assert_eq!(pair.ty.valid_range(), None);
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "valid_range_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    use std::num::NonZeroU8;
+
+    pub fn niches(
+        _non_zero: NonZeroU8,
+        _option: Option<NonZeroU8>,
+        _byte: u8,
+        _boolean: bool,
+        _pair: (u8, u32),
+    ) {{}}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,111 @@
+//@ run-pass
+//! Test that users are able to use typed variant and field indices in projections.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate serde_json;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::{FieldIdx, MirVisitor, Place, ProjectionElem};
+use stable_mir::mir::visit::{Location, PlaceContext};
+use stable_mir::ty::{IndexedVal, VariantIdx};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let body = get_item(&items, "second").unwrap().body();
+    let mut visitor = ProjectionCollector { projections: vec![] };
+    visitor.visit_body(&body);
+
+    let (variant, field) = visitor
+        .projections
+        .iter()
+        .find_map(|projection| match projection.as_slice() {
+            [ProjectionElem::Downcast(variant), ProjectionElem::Field(field, _)] => {
+                Some((*variant, *field))
+            }
+            _ => None,
+        })
+        .expect("Expected a field projection of a downcast");
+    assert_eq!(variant, VariantIdx::to_val(1));
+    assert_eq!(field, FieldIdx::to_val(0));
+
+    // The indices are serialized as plain numbers, which can be converted back.
+    let json = serde_json::to_value(ProjectionElem::Downcast(variant)).unwrap();
+    let index = json["Downcast"].as_u64().unwrap() as usize;
+    assert_eq!(VariantIdx::to_val(index), variant);
+
+    let json = serde_json::to_value(field).unwrap();
+    assert_eq!(FieldIdx::to_val(json.as_u64().unwrap() as usize), field);
+    ControlFlow::Continue(())
+}
+
+struct ProjectionCollector {
+    projections: Vec<Vec<ProjectionElem>>,
+}
+
+impl MirVisitor for ProjectionCollector {
+    fn visit_place(&mut self, place: &Place, _ptx: PlaceContext, _loc: Location) {
+        self.projections.push(place.projection.clone());
+    }
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "variant_field_idx_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub enum Either {{
+        First(u8),
+        Second(u16),
+    }}
+
+    pub fn second(value: Either) -> u16 {{
+        match value {{
+            Either::Second(inner) => inner,
+            Either::First(_) => 0,
+        }}
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,126 @@
+//@ run-pass
+//! Test that users are able to retrieve the visibility of items.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    let public = get_item(&items, "public_fn").unwrap();
+    assert_eq!(public.visibility(), Visibility::Public);
+    assert!(public.is_exported());
+
+    let private = get_item(&items, "private_fn").unwrap();
+    assert_eq!(private.visibility(), Visibility::Private);
+    assert!(!private.is_exported());
+
+    // `pub` items in a private module are not exported.
+    let hidden = get_item(&items, "inner::hidden").unwrap();
+    assert_eq!(hidden.visibility(), Visibility::Public);
+    assert!(!hidden.is_exported());
+
+    // Restricted items report the module where they are visible.
+    let in_crate = restricted_module(&items, "inner::in_crate");
+    let super_module = restricted_module(&items, "inner::deeper::in_super");
+    let path_module = restricted_module(&items, "inner::deeper::in_path");
+    assert_eq!(super_module, path_module);
+    assert_ne!(in_crate, super_module);
+    assert!(!get_item(&items, "inner::in_crate").unwrap().is_exported());
+
+    // Closures and anonymous constants have the visibility of the item that contains them.
+    let nested: Vec<_> =
+        items.iter().filter(|item| item.name().starts_with("public_fn::")).collect();
+    let mut kinds: Vec<_> = nested.iter().map(|item| item.kind()).collect();
+    kinds.sort_by_key(|kind| format!("{kind:?}"));
+    assert_eq!(kinds, [ItemKind::Const, ItemKind::Fn], "Unexpected items: {nested:?}");
+    for item in nested {
+        assert_eq!(item.visibility(), Visibility::Public);
+        assert!(!item.is_exported());
+    }
+    ControlFlow::Continue(())
+}
+
+fn restricted_module(items: &CrateItems, name: &str) -> DefId {
+    let visibility = get_item(items, name).unwrap().visibility();
+    let Visibility::Restricted(module) = visibility else {
+        panic!("Expected a restricted visibility for `{name}`, but found {visibility:?}")
+    };
+    module
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "visibility_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn public_fn() {{
+        let _closure = || private_fn();
+        let _array = [0u8; 1 + 1];
+        private_fn();
+        inner::hidden();
+        inner::in_crate();
+    }}
+
+    fn private_fn() {{}}
+
+    mod inner {{
+        pub fn hidden() {{
+            deeper::in_super();
+            deeper::in_path();
+        }}
+
+        pub(crate) fn in_crate() {{}}
+
+        pub(crate) mod deeper {{
+            pub(super) fn in_super() {{}}
+
+            pub(in crate::inner) fn in_path() {{}}
+        }}
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,114 @@
+//@ run-pass
+//! Test that users are able to visit the allocations referenced by constants.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::alloc::{AllocId, GlobalAlloc};
+use stable_mir::mir::visit::{Location, MirVisitor};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    // The string literal is stored in its own allocation.
+    let allocs = visited_allocs(&items, "greeting");
+    assert_eq!(allocs.len(), 1);
+    let GlobalAlloc::Memory(alloc) = GlobalAlloc::from(allocs[0]) else {
+        panic!("Expected the memory of the string literal")
+    };
+    assert_eq!(alloc.raw_bytes().unwrap(), b"hello");
+
+    // A reference to a static points to the static.
+    let allocs = visited_allocs(&items, "counter");
+    assert_eq!(allocs.len(), 1);
+    let GlobalAlloc::Static(def) = GlobalAlloc::from(allocs[0]) else {
+        panic!("Expected a static")
+    };
+    assert_eq!(def.name(), "COUNTER");
+
+    // Scalar constants don't reference any allocation.
+    assert!(visited_allocs(&items, "answer").is_empty());
+    ControlFlow::Continue(())
+}
+
+fn visited_allocs(items: &CrateItems, name: &str) -> Vec<AllocId> {
+    let body = get_item(items, name).unwrap().body();
+    let mut visitor = AllocVisitor { allocs: vec![] };
+    visitor.visit_body(&body);
+    visitor.allocs
+}
+
+struct AllocVisitor {
+    allocs: Vec<AllocId>,
+}
+
+impl MirVisitor for AllocVisitor {
+    fn visit_alloc_id(&mut self, alloc_id: &AllocId, _location: Location) {
+        self.allocs.push(*alloc_id);
+        self.super_alloc_id(alloc_id);
+    }
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "visit_alloc_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub static COUNTER: u32 = 0;
+
+    pub fn greeting() -> &'static str {{
+        "hello"
+    }}
+
+    pub fn counter() -> &'static u32 {{
+        &COUNTER
+    }}
+
+    pub fn answer() -> u32 {{
+        42
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,106 @@
+//@ run-pass
+//! Test that users are able to visit constants without visiting their types.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::visit::Location;
+use stable_mir::mir::{Body, MirVisitor};
+use stable_mir::ty::{MirConst, Ty};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+    let item = items.iter().find(|item| item.trimmed_name() == "constants").unwrap();
+    let body = item.body();
+
+    let all = count(&body, true);
+    let consts_only = count(&body, false);
+    assert!(all.consts > 0);
+    assert_eq!(all.consts, consts_only.consts);
+
+    // The type of each constant is visited exactly once by default.
+    assert_eq!(all.tys, consts_only.tys + all.consts);
+    ControlFlow::Continue(())
+}
+
+fn count(body: &Body, visit_const_tys: bool) -> Counter {
+    let mut counter = Counter { visit_const_tys, consts: 0, tys: 0 };
+    counter.visit_body(body);
+    counter
+}
+
+/// Count the number of constants and types visited.
+struct Counter {
+    visit_const_tys: bool,
+    consts: usize,
+    tys: usize,
+}
+
+impl MirVisitor for Counter {
+    fn visit_mir_const(&mut self, constant: &MirConst, location: Location) {
+        self.consts += 1;
+        self.super_mir_const(constant, location)
+    }
+
+    fn visit_mir_const_ty(&mut self, ty: &Ty, location: Location) {
+        if self.visit_const_tys {
+            self.visit_ty(ty, location)
+        }
+    }
+
+    fn visit_ty(&mut self, ty: &Ty, _location: Location) {
+        self.tys += 1;
+        self.super_ty(ty)
+    }
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "visit_const_ty_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub fn constants(x: u32) -> (u32, &'static str) {{
+        let y = x.wrapping_mul(3).wrapping_add(10);
+        (y, "hello")
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -0,0 +1,125 @@
+//@ run-pass
+//! Test that users are able to retrieve the trait and arguments of a vtable.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::mir::alloc::GlobalAlloc;
+use stable_mir::mir::mono::StaticDef;
+use stable_mir::ty::{
+    Binder, ExistentialPredicate, ExistentialTraitRef, GenericArgKind, RigidTy, TermKind, Ty,
+    TyKind, UintTy,
+};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let items = stable_mir::all_local_items();
+
+    // The trait reference of `dyn Iterator<Item = u8>` has no arguments, since `Self` and the
+    // associated type binding are not part of it.
+    let iter = get_static(&items, "ITER");
+    let (ty, trait_ref) = vtable(iter);
+    let (adt, _) = ty.as_adt().unwrap();
+    assert_eq!(adt.trimmed_name(), "Counter");
+    let trait_ref = trait_ref.unwrap().value;
+    assert_eq!(trait_ref.trait_def().trimmed_name(), "Iterator");
+    assert!(trait_ref.args().0.is_empty());
+
+    // The binding is part of the predicates of the trait object type.
+    let TyKind::RigidTy(RigidTy::Ref(_, dyn_ty, _)) = iter.ty().kind() else { unreachable!() };
+    let TyKind::RigidTy(RigidTy::Dynamic(predicates, ..)) = dyn_ty.kind() else { unreachable!() };
+    let item_ty = predicates
+        .iter()
+        .find_map(|predicate| match &predicate.value {
+            ExistentialPredicate::Projection(projection) => Some(projection.term.clone()),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(item_ty, TermKind::Type(Ty::unsigned_ty(UintTy::U8)));
+
+    // Generic arguments of the trait are included.
+    let (_, trait_ref) = vtable(get_static(&items, "BYTES"));
+    let trait_ref = trait_ref.unwrap().value;
+    assert_eq!(trait_ref.trait_def().trimmed_name(), "AsRef");
+    let [GenericArgKind::Type(arg)] = trait_ref.args().0.as_slice() else {
+        panic!("Unexpected arguments: {:?}", trait_ref.args())
+    };
+    assert_eq!(arg.kind().rigid(), Some(&RigidTy::Slice(Ty::unsigned_ty(UintTy::U8))));
+    ControlFlow::Continue(())
+}
+
+/// Find the vtable referenced by the given static.
+fn vtable(def: StaticDef) -> (Ty, Option<Binder<ExistentialTraitRef>>) {
+    def.reachable_allocs()
+        .unwrap()
+        .into_iter()
+        .find_map(|(_, global_alloc)| match global_alloc {
+            GlobalAlloc::VTable(ty, trait_ref) => Some((ty, trait_ref)),
+            _ => None,
+        })
+        .unwrap()
+}
+
+fn get_static(items: &CrateItems, name: &str) -> StaticDef {
+    let item = items.iter().find(|crate_item| crate_item.trimmed_name() == name).unwrap();
+    StaticDef::try_from(item).unwrap()
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "vtable_trait_ref_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    pub struct Counter;
+
+    impl Iterator for Counter {{
+        type Item = u8;
+
+        fn next(&mut self) -> Option<u8> {{
+            None
+        }}
+    }}
+
+    pub static ITER: &(dyn Iterator<Item = u8> + Sync) = &Counter;
+
+    pub static BYTES: &(dyn AsRef<[u8]> + Sync) = &[1u8, 2];
+    "#
+    )?;
+    Ok(())
+}
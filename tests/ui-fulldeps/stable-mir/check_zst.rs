@@ -0,0 +1,84 @@
+//@ run-pass
+//! Test that users are able to check whether a type is zero-sized.
+
+//@ ignore-stage1
+//@ ignore-cross-compile
+//@ ignore-remote
+//@ ignore-windows-gnu mingw has troubles with linking https://github.com/rust-lang/rust/pull/116837
+//@ edition: 2021
+
+#![feature(rustc_private)]
+#![feature(assert_matches)]
+
+extern crate rustc_middle;
+#[macro_use]
+extern crate rustc_smir;
+extern crate rustc_driver;
+extern crate rustc_interface;
+extern crate stable_mir;
+
+use rustc_smir::rustc_internal;
+use stable_mir::ty::{Ty, UintTy};
+use stable_mir::*;
+use std::io::Write;
+use std::ops::ControlFlow;
+
+const CRATE_NAME: &str = "input";
+
+/// This function uses the Stable MIR APIs to get information about the test crate.
+fn test_stable_mir() -> ControlFlow<()> {
+    let u8_ty = Ty::unsigned_ty(UintTy::U8);
+    assert_eq!(Ty::new_tuple(&[]).is_zst(), Ok(true));
+    assert_eq!(Ty::try_new_array(u8_ty, 0).unwrap().is_zst(), Ok(true));
+    assert_eq!(u8_ty.is_zst(), Ok(false));
+    assert_eq!(Ty::try_new_array(u8_ty, 4).unwrap().is_zst(), Ok(false));
+
+    let items = stable_mir::all_local_items();
+    let marker = get_item(&items, "marker").unwrap().body();
+    assert_eq!(marker.ret_local().ty.is_zst(), Ok(true));
+
+    // The layout of a generic type is unknown.
+    let generic = get_item(&items, "generic").unwrap().body();
+    assert!(generic.ret_local().ty.is_zst().is_err());
+    ControlFlow::Continue(())
+}
+
+fn get_item<'a>(items: &'a CrateItems, name: &str) -> Option<&'a CrateItem> {
+    items.iter().find(|crate_item| crate_item.trimmed_name() == name)
+}
+
+/// This test will generate and analyze a dummy crate using the stable mir.
+/// For that, it will first write the dummy crate into a file.
+/// Then it will create a `StableMir` using custom arguments and then
+/// it will run the compiler.
+fn main() {
+    let path = "zst_input.rs";
+    generate_input(&path).unwrap();
+    let args = vec![
+        "rustc".to_string(),
+        "--crate-type=lib".to_string(),
+        "--crate-name".to_string(),
+        CRATE_NAME.to_string(),
+        path.to_string(),
+    ];
+    run!(args, test_stable_mir).unwrap();
+}
+
+fn generate_input(path: &str) -> std::io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    write!(
+        file,
+        r#"
+    use std::marker::PhantomData;
+
+    pub fn marker() -> PhantomData<u64> {{
+        PhantomData
+    }}
+
+    pub fn generic<T: Default>() -> T {{
+        T::default()
+    }}
+    "#
+    )?;
+    Ok(())
+}
@@ -21,7 +21,7 @@
 use rustc_smir::rustc_internal;
 use stable_mir::crate_def::CrateDef;
 use stable_mir::mir::{ProjectionElem, Rvalue, StatementKind};
-use stable_mir::ty::{RigidTy, TyKind, UintTy};
+use stable_mir::ty::{IndexedVal, RigidTy, TyKind, UintTy};
 use stable_mir::ItemKind;
 use std::assert_matches::assert_matches;
 use std::io::Write;
@@ -47,7 +47,7 @@ fn test_place_projections() -> ControlFlow<()> {
             assert!(local_proj.is_empty());
             match &r_proj[..] {
                 // Similarly we can't match against a type, only against its kind.
-                [ProjectionElem::Deref, ProjectionElem::Field(2, ty)] => {
+                [ProjectionElem::Deref, ProjectionElem::Field(idx, ty)] if idx.to_index() == 2 => {
                     assert_matches!(
                         ty.kind(),
                         TyKind::RigidTy(RigidTy::Uint(stable_mir::ty::UintTy::U8))